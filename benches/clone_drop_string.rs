@@ -0,0 +1,78 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    time::Duration,
+};
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId,
+    Criterion,
+};
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+
+fn bench_clone_and_drop<T: Clone>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    parameter: &str,
+    value: &T,
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("{} clone", name), parameter),
+        value,
+        |b, value| b.iter_with_large_drop(|| value.clone()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("{} drop", name), parameter),
+        value,
+        |b, value| b.iter_batched(|| value.clone(), drop, BatchSize::SmallInput),
+    );
+}
+
+fn bench_clone_drop_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone_drop_string");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [8, 128, 2048].into_iter() {
+        let items = (0..item_count)
+            .map(|_| {
+                let len = rng.gen_range(2..32);
+                (&mut rng)
+                    .sample_iter(&Alphanumeric)
+                    .take(len)
+                    .map(char::from)
+                    .collect::<String>()
+            })
+            .collect_vec();
+        let parameter = item_count.to_string();
+        bench_clone_and_drop(&mut group, "Vec", &parameter, &items);
+        let hashset: HashSet<_> = items.iter().cloned().collect();
+        bench_clone_and_drop(&mut group, "HashSet", &parameter, &hashset);
+        let btreeset: BTreeSet<_> = items.iter().cloned().collect();
+        bench_clone_and_drop(&mut group, "BTreeSet", &parameter, &btreeset);
+        let indexset: IndexSet<_> = items.iter().cloned().collect();
+        bench_clone_and_drop(&mut group, "IndexSet", &parameter, &indexset);
+        // Same shape as the `group_by` outputs: 8 values per key.
+        let mut hashmap: HashMap<String, Vec<String>> = HashMap::new();
+        let mut indexmap: IndexMap<String, Vec<String>> = IndexMap::new();
+        for (index, value) in items.iter().enumerate() {
+            hashmap
+                .entry(items[index / 8].clone())
+                .or_default()
+                .push(value.clone());
+            indexmap
+                .entry(items[index / 8].clone())
+                .or_default()
+                .push(value.clone());
+        }
+        bench_clone_and_drop(&mut group, "Hashmap", &parameter, &hashmap);
+        bench_clone_and_drop(&mut group, "Indexmap", &parameter, &indexmap);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_clone_drop_string);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    time::Duration,
+};
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId,
+    Criterion,
+};
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+fn bench_clone_and_drop<T: Clone>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    parameter: &str,
+    value: &T,
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("{} clone", name), parameter),
+        value,
+        |b, value| b.iter_with_large_drop(|| value.clone()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("{} drop", name), parameter),
+        value,
+        |b, value| b.iter_batched(|| value.clone(), drop, BatchSize::SmallInput),
+    );
+}
+
+fn bench_clone_drop_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone_drop_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [8, 128, 2048].into_iter() {
+        let items = (0..item_count).map(|_| rng.next_u64()).collect_vec();
+        let parameter = item_count.to_string();
+        bench_clone_and_drop(&mut group, "Vec", &parameter, &items);
+        let hashset: HashSet<_> = items.iter().cloned().collect();
+        bench_clone_and_drop(&mut group, "HashSet", &parameter, &hashset);
+        let btreeset: BTreeSet<_> = items.iter().cloned().collect();
+        bench_clone_and_drop(&mut group, "BTreeSet", &parameter, &btreeset);
+        let indexset: IndexSet<_> = items.iter().cloned().collect();
+        bench_clone_and_drop(&mut group, "IndexSet", &parameter, &indexset);
+        // Same shape as the `group_by` outputs: 8 values per key.
+        let mut hashmap: HashMap<u64, Vec<u64>> = HashMap::new();
+        let mut indexmap: IndexMap<u64, Vec<u64>> = IndexMap::new();
+        for (index, value) in items.iter().enumerate() {
+            hashmap.entry(items[index / 8]).or_default().push(*value);
+            indexmap.entry(items[index / 8]).or_default().push(*value);
+        }
+        bench_clone_and_drop(&mut group, "Hashmap", &parameter, &hashmap);
+        bench_clone_and_drop(&mut group, "Indexmap", &parameter, &indexmap);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_clone_drop_trivial);
+criterion_main!(benches);
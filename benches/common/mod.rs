@@ -0,0 +1,69 @@
+// Every bench compiles its own copy of this module and only uses part of it.
+#![allow(dead_code)]
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// Dedicated pools for the thread-count axis shared by the parallel variants.
+pub fn thread_pools() -> Vec<(usize, ThreadPool)> {
+    [1, 2, 4, 8]
+        .into_iter()
+        .map(|threads| {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            (threads, pool)
+        })
+        .collect()
+}
+
+/// Draws an id whose spread over the id space follows `density`: `dense` ids fall within twice
+/// the item count, `clustered` ones come in short runs and `sparse` ones span 32 bits.
+pub fn gen_id(rng: &mut StdRng, density: &str, item_count: u64) -> u64 {
+    match density {
+        "dense" => rng.gen_range(0..item_count * 2),
+        // Runs within 128 ids of a base, with bases 64Ki apart.
+        "clustered" => rng.gen_range(0..item_count.div_ceil(32)) * 0x1_0000 + rng.gen_range(0..128),
+        _ => rng.gen_range(0..0x1_0000_0000),
+    }
+}
+
+/// Builds key-major `(key, value)` pairs with `per_key` values for each of `key_count` keys.
+pub fn group_by_source(rng: &mut StdRng, key_count: u64, per_key: usize) -> Vec<(u64, u64)> {
+    let keys = (0..key_count).map(|_| rng.next_u64()).collect_vec();
+    let mut source = Vec::with_capacity(keys.len() * per_key);
+    for key in keys {
+        for _ in 0..per_key {
+            // 32-bit values keep per-key sums far from overflowing.
+            source.push((key, rng.gen_range(0..0x1_0000_0000u64)));
+        }
+    }
+    source
+}
+
+/// Lays out key-major `contiguous` input, with `per_key` values per key, in the requested order.
+pub fn arrange<T: Clone>(
+    contiguous: &[T],
+    per_key: usize,
+    order: &str,
+    rng: &mut StdRng,
+) -> Vec<T> {
+    match order {
+        "contiguous" => contiguous.to_vec(),
+        "clustered" => {
+            let mut runs = contiguous.chunks(8).collect_vec();
+            runs.shuffle(rng);
+            runs.concat()
+        }
+        "shuffled" => {
+            let mut source = contiguous.to_vec();
+            source.shuffle(rng);
+            source
+        }
+        _ => (0..per_key)
+            .flat_map(|i| contiguous.iter().skip(i).step_by(per_key).cloned())
+            .collect(),
+    }
+}
@@ -0,0 +1,465 @@
+mod common;
+
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    sync::{Mutex, RwLock},
+    time::Duration,
+};
+
+use arc_swap::ArcSwap;
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId,
+    Criterion, Throughput,
+};
+use dashmap::{DashMap, DashSet};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::ThreadPool;
+
+type Groups = HashMap<u64, Vec<u64>>;
+
+const OPS_PER_THREAD: usize = 4096;
+const SHARD_COUNT: usize = 16;
+
+#[derive(Clone, Copy)]
+enum SetOp {
+    Contains(u64),
+    Insert(u64),
+}
+
+#[derive(Clone, Copy)]
+enum GroupOp {
+    Len(u64),
+    Push(u64, u64),
+}
+
+trait ConcurrentSet: Sync {
+    fn new(items: &[u64]) -> Self;
+    fn contains(&self, key: u64) -> bool;
+    fn insert(&self, key: u64);
+}
+
+trait ConcurrentGroups: Sync {
+    fn new(source: &[(u64, u64)]) -> Self;
+    fn group_len(&self, key: u64) -> usize;
+    fn push(&self, key: u64, value: u64);
+}
+
+fn hashset(items: &[u64]) -> HashSet<u64> {
+    items.iter().cloned().collect()
+}
+
+fn groups(source: &[(u64, u64)]) -> Groups {
+    let mut result: Groups = HashMap::new();
+    for (k, v) in source {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+impl ConcurrentSet for Mutex<HashSet<u64>> {
+    fn new(items: &[u64]) -> Self {
+        Mutex::new(hashset(items))
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        self.lock().unwrap().contains(&key)
+    }
+
+    fn insert(&self, key: u64) {
+        self.lock().unwrap().insert(key);
+    }
+}
+
+impl ConcurrentSet for RwLock<HashSet<u64>> {
+    fn new(items: &[u64]) -> Self {
+        RwLock::new(hashset(items))
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        self.read().unwrap().contains(&key)
+    }
+
+    fn insert(&self, key: u64) {
+        self.write().unwrap().insert(key);
+    }
+}
+
+/// A fixed array of independently locked shards, picked by the top bits of a Fibonacci hash.
+struct Sharded<T> {
+    shards: Vec<RwLock<T>>,
+}
+
+impl<T: Default> Sharded<T> {
+    fn with_shards() -> Self {
+        Self {
+            shards: iter::repeat_with(Default::default)
+                .take(SHARD_COUNT)
+                .collect(),
+        }
+    }
+
+    fn shard(&self, key: u64) -> &RwLock<T> {
+        let index = key.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (64 - SHARD_COUNT.trailing_zeros());
+        &self.shards[index as usize]
+    }
+}
+
+impl ConcurrentSet for Sharded<HashSet<u64>> {
+    fn new(items: &[u64]) -> Self {
+        let set = Self::with_shards();
+        for it in items {
+            set.shard(*it).write().unwrap().insert(*it);
+        }
+        set
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        self.shard(key).read().unwrap().contains(&key)
+    }
+
+    fn insert(&self, key: u64) {
+        self.shard(key).write().unwrap().insert(key);
+    }
+}
+
+impl ConcurrentSet for DashSet<u64> {
+    fn new(items: &[u64]) -> Self {
+        items.iter().cloned().collect()
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        DashSet::contains(self, &key)
+    }
+
+    fn insert(&self, key: u64) {
+        DashSet::insert(self, key);
+    }
+}
+
+impl ConcurrentSet for ArcSwap<HashSet<u64>> {
+    fn new(items: &[u64]) -> Self {
+        ArcSwap::from_pointee(hashset(items))
+    }
+
+    fn contains(&self, key: u64) -> bool {
+        self.load().contains(&key)
+    }
+
+    // Writers copy the whole set and publish it, so readers never wait on a lock.
+    fn insert(&self, key: u64) {
+        self.rcu(|set| {
+            let mut set = HashSet::clone(set);
+            set.insert(key);
+            set
+        });
+    }
+}
+
+impl ConcurrentGroups for Mutex<Groups> {
+    fn new(source: &[(u64, u64)]) -> Self {
+        Mutex::new(groups(source))
+    }
+
+    fn group_len(&self, key: u64) -> usize {
+        self.lock().unwrap().get(&key).map_or(0, |it| it.len())
+    }
+
+    fn push(&self, key: u64, value: u64) {
+        self.lock().unwrap().entry(key).or_default().push(value);
+    }
+}
+
+impl ConcurrentGroups for RwLock<Groups> {
+    fn new(source: &[(u64, u64)]) -> Self {
+        RwLock::new(groups(source))
+    }
+
+    fn group_len(&self, key: u64) -> usize {
+        self.read().unwrap().get(&key).map_or(0, |it| it.len())
+    }
+
+    fn push(&self, key: u64, value: u64) {
+        self.write().unwrap().entry(key).or_default().push(value);
+    }
+}
+
+impl ConcurrentGroups for Sharded<Groups> {
+    fn new(source: &[(u64, u64)]) -> Self {
+        let map = Self::with_shards();
+        for (k, v) in source {
+            map.push(*k, *v);
+        }
+        map
+    }
+
+    fn group_len(&self, key: u64) -> usize {
+        self.shard(key)
+            .read()
+            .unwrap()
+            .get(&key)
+            .map_or(0, |it| it.len())
+    }
+
+    fn push(&self, key: u64, value: u64) {
+        self.shard(key)
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .push(value);
+    }
+}
+
+impl ConcurrentGroups for DashMap<u64, Vec<u64>> {
+    fn new(source: &[(u64, u64)]) -> Self {
+        let map = DashMap::new();
+        for (k, v) in source {
+            map.push(*k, *v);
+        }
+        map
+    }
+
+    fn group_len(&self, key: u64) -> usize {
+        self.get(&key).map_or(0, |it| it.len())
+    }
+
+    fn push(&self, key: u64, value: u64) {
+        self.entry(key).or_default().push(value);
+    }
+}
+
+impl ConcurrentGroups for ArcSwap<Groups> {
+    fn new(source: &[(u64, u64)]) -> Self {
+        ArcSwap::from_pointee(groups(source))
+    }
+
+    fn group_len(&self, key: u64) -> usize {
+        self.load().get(&key).map_or(0, |it| it.len())
+    }
+
+    fn push(&self, key: u64, value: u64) {
+        self.rcu(|map| {
+            let mut map = Groups::clone(map);
+            map.entry(key).or_default().push(value);
+            map
+        });
+    }
+}
+
+fn run_set<S: ConcurrentSet>(set: &S, ops: &[SetOp]) -> usize {
+    let mut hits = 0;
+    for op in ops {
+        match *op {
+            SetOp::Contains(key) => hits += set.contains(key) as usize,
+            SetOp::Insert(key) => set.insert(key),
+        }
+    }
+    hits
+}
+
+fn run_groups<G: ConcurrentGroups>(map: &G, ops: &[GroupOp]) -> usize {
+    let mut total = 0;
+    for op in ops {
+        match *op {
+            GroupOp::Len(key) => total += map.group_len(key),
+            GroupOp::Push(key, value) => map.push(key, value),
+        }
+    }
+    total
+}
+
+// Every iteration starts from a fresh structure, so writes from earlier iterations never pile up.
+fn bench_set<S: ConcurrentSet>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    parameter: &str,
+    pool: &ThreadPool,
+    items: &[u64],
+    ops: &[Vec<SetOp>],
+) {
+    group.bench_with_input(BenchmarkId::new(name, parameter), ops, |b, ops| {
+        b.iter_batched(
+            || S::new(items),
+            |set| {
+                pool.broadcast(|context| run_set(&set, &ops[context.index()]));
+                set
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_groups<G: ConcurrentGroups>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    parameter: &str,
+    pool: &ThreadPool,
+    source: &[(u64, u64)],
+    ops: &[Vec<GroupOp>],
+) {
+    group.bench_with_input(BenchmarkId::new(name, parameter), ops, |b, ops| {
+        b.iter_batched(
+            || G::new(source),
+            |map| {
+                pool.broadcast(|context| run_groups(&map, &ops[context.index()]));
+                map
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_concurrent_contains_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("concurrent_contains_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    let pools = common::thread_pools();
+    for item_count in [1024, 16384].into_iter() {
+        let items = iter::repeat_with(|| common::gen_id(&mut rng, "sparse", item_count))
+            .unique()
+            .take(item_count as usize)
+            .collect_vec();
+        for write_ratio in [0, 1, 10, 50] {
+            for (threads, pool) in &pools {
+                // Reads look up a known item half of the time and a fresh id otherwise.
+                let ops = (0..*threads)
+                    .map(|_| {
+                        (0..OPS_PER_THREAD)
+                            .map(|_| {
+                                if rng.gen_ratio(write_ratio, 100) {
+                                    SetOp::Insert(common::gen_id(&mut rng, "sparse", item_count))
+                                } else if rng.gen_ratio(1, 2) {
+                                    SetOp::Contains(items[rng.gen_range(0..items.len())])
+                                } else {
+                                    SetOp::Contains(common::gen_id(&mut rng, "sparse", item_count))
+                                }
+                            })
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                let parameter =
+                    format!("{},{}% writes,{} threads", item_count, write_ratio, threads);
+                group.throughput(Throughput::Elements((threads * OPS_PER_THREAD) as u64));
+                bench_set::<Mutex<HashSet<u64>>>(
+                    &mut group,
+                    "Mutex<HashSet>",
+                    &parameter,
+                    pool,
+                    &items,
+                    &ops,
+                );
+                bench_set::<RwLock<HashSet<u64>>>(
+                    &mut group,
+                    "RwLock<HashSet>",
+                    &parameter,
+                    pool,
+                    &items,
+                    &ops,
+                );
+                bench_set::<Sharded<HashSet<u64>>>(
+                    &mut group,
+                    "sharded RwLock<HashSet>",
+                    &parameter,
+                    pool,
+                    &items,
+                    &ops,
+                );
+                bench_set::<DashSet<u64>>(&mut group, "DashSet", &parameter, pool, &items, &ops);
+                bench_set::<ArcSwap<HashSet<u64>>>(
+                    &mut group,
+                    "ArcSwap<HashSet>",
+                    &parameter,
+                    pool,
+                    &items,
+                    &ops,
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+fn bench_concurrent_group_by_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("concurrent_group_by_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    let pools = common::thread_pools();
+    for key_count in [32, 128].into_iter() {
+        // Each key gets `key_count` values, so the maps start out holding
+        // `key_count * key_count` pairs.
+        let source = common::group_by_source(&mut rng, key_count, key_count as usize);
+        let keys = source.iter().map(|(key, _)| *key).dedup().collect_vec();
+        for write_ratio in [0, 1, 10, 50] {
+            for (threads, pool) in &pools {
+                let ops = (0..*threads)
+                    .map(|_| {
+                        (0..OPS_PER_THREAD)
+                            .map(|_| {
+                                let key = keys[rng.gen_range(0..keys.len())];
+                                if rng.gen_ratio(write_ratio, 100) {
+                                    GroupOp::Push(key, rng.gen_range(0..0x1_0000_0000))
+                                } else {
+                                    GroupOp::Len(key)
+                                }
+                            })
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                let parameter =
+                    format!("{},{}% writes,{} threads", key_count, write_ratio, threads);
+                group.throughput(Throughput::Elements((threads * OPS_PER_THREAD) as u64));
+                bench_groups::<Mutex<Groups>>(
+                    &mut group,
+                    "Mutex<Hashmap>",
+                    &parameter,
+                    pool,
+                    &source,
+                    &ops,
+                );
+                bench_groups::<RwLock<Groups>>(
+                    &mut group,
+                    "RwLock<Hashmap>",
+                    &parameter,
+                    pool,
+                    &source,
+                    &ops,
+                );
+                bench_groups::<Sharded<Groups>>(
+                    &mut group,
+                    "sharded RwLock<Hashmap>",
+                    &parameter,
+                    pool,
+                    &source,
+                    &ops,
+                );
+                bench_groups::<DashMap<u64, Vec<u64>>>(
+                    &mut group, "DashMap", &parameter, pool, &source, &ops,
+                );
+                bench_groups::<ArcSwap<Groups>>(
+                    &mut group,
+                    "ArcSwap<Hashmap>",
+                    &parameter,
+                    pool,
+                    &source,
+                    &ops,
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_concurrent_contains_trivial,
+    bench_concurrent_group_by_trivial
+);
+criterion_main!(benches);
@@ -1,9 +1,25 @@
-use std::{collections::HashSet, time::Duration};
+use std::{
+    collections::{hash_map::RandomState, BTreeSet, HashSet},
+    hash::{BuildHasher, BuildHasherDefault},
+    ops::Bound,
+    time::Duration,
+};
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use fst::{
+    automaton::{Automaton, Str},
+    IntoStreamer,
+};
 use indexmap::IndexSet;
 use itertools::Itertools;
+use phf_shared::HashKey;
+use radix_trie::{Trie, TrieCommon};
 use rand::{distributions::Alphanumeric, rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rustc_hash::FxHasher;
+
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
 fn vec(source: &[String], values: &[String]) -> Vec<bool> {
     let vec = source.iter().cloned().collect_vec();
@@ -19,16 +35,268 @@ fn sorted_vec(source: &[String], values: &[String]) -> Vec<bool> {
         .collect()
 }
 
-fn hashset(source: &[String], values: &[String]) -> Vec<bool> {
-    let set: HashSet<_> = source.iter().cloned().collect();
+fn hashset<S: BuildHasher + Default>(source: &[String], values: &[String]) -> Vec<bool> {
+    let set: HashSet<_, S> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn indexset<S: BuildHasher + Default>(source: &[String], values: &[String]) -> Vec<bool> {
+    let set: IndexSet<_, S> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn hashbrown(source: &[String], values: &[String]) -> Vec<bool> {
+    let set: hashbrown::HashSet<_> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn im_hashset(source: &[String], values: &[String]) -> Vec<bool> {
+    let set: im::HashSet<String> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn im_ordset(source: &[String], values: &[String]) -> Vec<bool> {
+    let set: im::OrdSet<String> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn rpds_hash_trie_set(source: &[String], values: &[String]) -> Vec<bool> {
+    let set: rpds::HashTrieSet<String> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn rpds_red_black_tree_set(source: &[String], values: &[String]) -> Vec<bool> {
+    let set: rpds::RedBlackTreeSet<String> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+struct PerfectHashSet {
+    key: HashKey,
+    disps: Vec<(u32, u32)>,
+    entries: Vec<String>,
+}
+
+impl PerfectHashSet {
+    fn new(source: &[String]) -> Self {
+        // Identical keys can never be separated, so they are dropped before generation.
+        let keys = source.iter().unique().collect_vec();
+        let state = phf_generator::generate_hash(&keys);
+        Self {
+            key: state.key,
+            disps: state.disps,
+            entries: state.map.iter().map(|it| keys[*it].clone()).collect(),
+        }
+    }
+
+    fn contains(&self, value: &str) -> bool {
+        if self.entries.is_empty() {
+            return false;
+        }
+        let hashes = phf_shared::hash(value, &self.key);
+        let index = phf_shared::get_index(&hashes, &self.disps, self.entries.len());
+        self.entries[index as usize] == value
+    }
+}
+
+fn perfect_hash(source: &[String], values: &[String]) -> Vec<bool> {
+    let set = PerfectHashSet::new(source);
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn perfect_hash_lookup(set: &PerfectHashSet, values: &[String]) -> Vec<bool> {
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn hashset_lookup(set: &HashSet<String>, values: &[String]) -> Vec<bool> {
     values.iter().map(|it| set.contains(it)).collect()
 }
 
-fn indexset(source: &[String], values: &[String]) -> Vec<bool> {
-    let set: IndexSet<_> = source.iter().cloned().collect();
+fn sorted_vec_lookup(vec: &[String], values: &[String]) -> Vec<bool> {
+    values
+        .iter()
+        .map(|it| vec.binary_search(it).is_ok())
+        .collect()
+}
+
+#[derive(Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    terminal: bool,
+}
+
+/// A byte-wise trie with nodes stored in an arena and children kept sorted by byte.
+struct ByteTrie {
+    nodes: Vec<TrieNode>,
+}
+
+impl ByteTrie {
+    fn new(source: &[String]) -> Self {
+        let mut trie = Self {
+            nodes: vec![TrieNode::default()],
+        };
+        for it in source {
+            let mut node = 0;
+            for byte in it.bytes() {
+                let children = &trie.nodes[node].children;
+                node = match children.binary_search_by_key(&byte, |child| child.0) {
+                    Ok(index) => children[index].1,
+                    Err(index) => {
+                        let child = trie.nodes.len();
+                        trie.nodes[node].children.insert(index, (byte, child));
+                        trie.nodes.push(TrieNode::default());
+                        child
+                    }
+                };
+            }
+            trie.nodes[node].terminal = true;
+        }
+        trie
+    }
+
+    fn find(&self, key: &str) -> Option<usize> {
+        key.bytes().try_fold(0, |node, byte| {
+            let children = &self.nodes[node].children;
+            children
+                .binary_search_by_key(&byte, |child| child.0)
+                .ok()
+                .map(|index| children[index].1)
+        })
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.find(key).is_some_and(|node| self.nodes[node].terminal)
+    }
+
+    fn with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut result = Vec::new();
+        if let Some(node) = self.find(prefix) {
+            self.collect(node, &mut prefix.as_bytes().to_vec(), &mut result);
+        }
+        result
+    }
+
+    fn collect(&self, node: usize, path: &mut Vec<u8>, result: &mut Vec<String>) {
+        if self.nodes[node].terminal {
+            result.push(String::from_utf8(path.clone()).unwrap());
+        }
+        for (byte, child) in &self.nodes[node].children {
+            path.push(*byte);
+            self.collect(*child, path, result);
+            path.pop();
+        }
+    }
+}
+
+fn radix_trie(source: &[String]) -> Trie<String, ()> {
+    let mut trie = Trie::new();
+    for it in source {
+        trie.insert(it.clone(), ());
+    }
+    trie
+}
+
+fn fst_set(source: &[String]) -> fst::Set<Vec<u8>> {
+    // `fst` requires its input in lexicographic order without duplicates.
+    fst::Set::from_iter(source.iter().sorted().dedup()).unwrap()
+}
+
+fn byte_trie(source: &[String], values: &[String]) -> Vec<bool> {
+    let trie = ByteTrie::new(source);
+    values.iter().map(|it| trie.contains(it)).collect()
+}
+
+fn radix_trie_contains(source: &[String], values: &[String]) -> Vec<bool> {
+    let trie = radix_trie(source);
+    values.iter().map(|it| trie.get(it).is_some()).collect()
+}
+
+fn fst_contains(source: &[String], values: &[String]) -> Vec<bool> {
+    let set = fst_set(source);
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn byte_trie_lookup(trie: &ByteTrie, values: &[String]) -> Vec<bool> {
+    values.iter().map(|it| trie.contains(it)).collect()
+}
+
+fn radix_trie_lookup(trie: &Trie<String, ()>, values: &[String]) -> Vec<bool> {
+    values.iter().map(|it| trie.get(it).is_some()).collect()
+}
+
+fn fst_lookup(set: &fst::Set<Vec<u8>>, values: &[String]) -> Vec<bool> {
     values.iter().map(|it| set.contains(it)).collect()
 }
 
+fn btreeset_prefix(set: &BTreeSet<String>, prefixes: &[String]) -> Vec<Vec<String>> {
+    prefixes
+        .iter()
+        .map(|prefix| {
+            set.range::<str, _>((Bound::Included(prefix.as_str()), Bound::Unbounded))
+                .take_while(|it| it.starts_with(prefix.as_str()))
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
+fn hashset_prefix(set: &HashSet<String>, prefixes: &[String]) -> Vec<Vec<String>> {
+    prefixes
+        .iter()
+        .map(|prefix| {
+            set.iter()
+                .filter(|it| it.starts_with(prefix.as_str()))
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
+fn byte_trie_prefix(trie: &ByteTrie, prefixes: &[String]) -> Vec<Vec<String>> {
+    prefixes.iter().map(|it| trie.with_prefix(it)).collect()
+}
+
+fn radix_trie_prefix(trie: &Trie<String, ()>, prefixes: &[String]) -> Vec<Vec<String>> {
+    prefixes
+        .iter()
+        .map(|prefix| {
+            // The closest descendant is the first node whose key extends `prefix`.
+            trie.get_raw_descendant(prefix)
+                .map_or_else(Vec::new, |subtrie| subtrie.keys().cloned().collect())
+        })
+        .collect()
+}
+
+fn fst_prefix(set: &fst::Set<Vec<u8>>, prefixes: &[String]) -> Vec<Vec<String>> {
+    prefixes
+        .iter()
+        .map(|prefix| {
+            set.search(Str::new(prefix).starts_with())
+                .into_stream()
+                .into_strs()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn bench_hashed<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    items: &[String],
+    find_items: &[String],
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("HashSet{}", hasher), parameter),
+        &(items, find_items),
+        |b, (items, find_items)| b.iter(|| hashset::<S>(items, find_items)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("IndexSet{}", hasher), parameter),
+        &(items, find_items),
+        |b, (items, find_items)| b.iter(|| indexset::<S>(items, find_items)),
+    );
+}
+
 fn bench_contains_string(c: &mut Criterion) {
     let mut group = c.benchmark_group("contains_string");
     group
@@ -47,6 +315,17 @@ fn bench_contains_string(c: &mut Criterion) {
                     .collect::<String>()
             })
             .collect_vec();
+        let perfect_hash_set = PerfectHashSet::new(&items);
+        let hash_set: HashSet<String> = items.iter().cloned().collect();
+        let sorted_items = items.iter().cloned().sorted().collect_vec();
+        let byte_trie_set = ByteTrie::new(&items);
+        let radix_trie_set = radix_trie(&items);
+        let fst_set = fst_set(&items);
+        group.bench_with_input(
+            BenchmarkId::new("perfect hash build", item_count),
+            &items,
+            |b, items| b.iter(|| PerfectHashSet::new(items)),
+        );
         for search_times in [8, 32, 128] {
             for exist_all_ration in [0.1f64, 0.5, 0.9].into_iter() {
                 let exist_count = ((search_times as f64) * exist_all_ration).round() as u64;
@@ -65,57 +344,111 @@ fn bench_contains_string(c: &mut Criterion) {
                     );
                 }
                 find_items.shuffle(&mut rng);
+                let parameter = format!(
+                    "{} times,{} items,{}% found",
+                    search_times,
+                    item_count,
+                    exist_all_ration * 100f64
+                );
                 group.bench_with_input(
-                    BenchmarkId::new(
-                        "Vec",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
+                    BenchmarkId::new("Vec", &parameter),
                     &(&items, &find_items),
                     |b, (items, find_items)| b.iter(|| vec(items, find_items)),
                 );
                 group.bench_with_input(
-                    BenchmarkId::new(
-                        "sorted vec",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
+                    BenchmarkId::new("sorted vec", &parameter),
                     &(&items, &find_items),
                     |b, (items, find_items)| b.iter(|| sorted_vec(items, find_items)),
                 );
                 group.bench_with_input(
-                    BenchmarkId::new(
-                        "HashSet",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
+                    BenchmarkId::new("im::HashSet", &parameter),
+                    &(&items, &find_items),
+                    |b, (items, find_items)| b.iter(|| im_hashset(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("im::OrdSet", &parameter),
+                    &(&items, &find_items),
+                    |b, (items, find_items)| b.iter(|| im_ordset(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("rpds::HashTrieSet", &parameter),
+                    &(&items, &find_items),
+                    |b, (items, find_items)| b.iter(|| rpds_hash_trie_set(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("rpds::RedBlackTreeSet", &parameter),
+                    &(&items, &find_items),
+                    |b, (items, find_items)| b.iter(|| rpds_red_black_tree_set(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("perfect hash", &parameter),
+                    &(&items, &find_items),
+                    |b, (items, find_items)| b.iter(|| perfect_hash(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("perfect hash lookup", &parameter),
+                    &find_items,
+                    |b, find_items| b.iter(|| perfect_hash_lookup(&perfect_hash_set, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("HashSet lookup", &parameter),
+                    &find_items,
+                    |b, find_items| b.iter(|| hashset_lookup(&hash_set, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sorted vec lookup", &parameter),
+                    &find_items,
+                    |b, find_items| b.iter(|| sorted_vec_lookup(&sorted_items, find_items)),
+                );
+                bench_hashed::<RandomState>(&mut group, "", &parameter, &items, &find_items);
+                bench_hashed::<FxBuildHasher>(
+                    &mut group,
+                    "+FxHash",
+                    &parameter,
+                    &items,
+                    &find_items,
+                );
+                bench_hashed::<ahash::RandomState>(
+                    &mut group,
+                    "+AHash",
+                    &parameter,
+                    &items,
+                    &find_items,
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("hashbrown::HashSet", &parameter),
+                    &(&items, &find_items),
+                    |b, (items, find_items)| b.iter(|| hashbrown(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("byte trie", &parameter),
+                    &(&items, &find_items),
+                    |b, (items, find_items)| b.iter(|| byte_trie(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("radix_trie::Trie", &parameter),
                     &(&items, &find_items),
-                    |b, (items, find_items)| b.iter(|| hashset(items, find_items)),
-                );
-                group.bench_with_input(
-                    BenchmarkId::new(
-                        "IndexSet",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
+                    |b, (items, find_items)| b.iter(|| radix_trie_contains(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("fst::Set", &parameter),
                     &(&items, &find_items),
-                    |b, (items, find_items)| b.iter(|| indexset(items, find_items)),
+                    |b, (items, find_items)| b.iter(|| fst_contains(items, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("byte trie lookup", &parameter),
+                    &find_items,
+                    |b, find_items| b.iter(|| byte_trie_lookup(&byte_trie_set, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("radix_trie::Trie lookup", &parameter),
+                    &find_items,
+                    |b, find_items| b.iter(|| radix_trie_lookup(&radix_trie_set, find_items)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("fst::Set lookup", &parameter),
+                    &find_items,
+                    |b, find_items| b.iter(|| fst_lookup(&fst_set, find_items)),
                 );
             }
         }
@@ -123,5 +456,68 @@ fn bench_contains_string(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_contains_string);
+fn bench_prefix_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prefix_string");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [1024, 16384].into_iter() {
+        let items = (0..item_count)
+            .map(|_| {
+                let len = rng.gen_range(2..32);
+                (&mut rng)
+                    .sample_iter(&Alphanumeric)
+                    .take(len)
+                    .map(char::from)
+                    .collect::<String>()
+            })
+            .collect_vec();
+        let btree_set: BTreeSet<String> = items.iter().cloned().collect();
+        let hash_set: HashSet<String> = items.iter().cloned().collect();
+        let byte_trie_set = ByteTrie::new(&items);
+        let radix_trie_set = radix_trie(&items);
+        let fst_set = fst_set(&items);
+        for prefix_len in [1, 2, 3] {
+            // Prefixes are cut from existing items, so every query matches at least once.
+            let long_enough = items
+                .iter()
+                .filter(|it| it.len() >= prefix_len)
+                .collect_vec();
+            let prefixes = (0..32)
+                .map(|_| long_enough[rng.gen_range(0..long_enough.len())][..prefix_len].to_string())
+                .collect_vec();
+            let parameter = format!("{} items,{} byte prefix", item_count, prefix_len);
+            group.bench_with_input(
+                BenchmarkId::new("BTreeSet::range", &parameter),
+                &prefixes,
+                |b, prefixes| b.iter(|| btreeset_prefix(&btree_set, prefixes)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("HashSet+filter", &parameter),
+                &prefixes,
+                |b, prefixes| b.iter(|| hashset_prefix(&hash_set, prefixes)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("byte trie", &parameter),
+                &prefixes,
+                |b, prefixes| b.iter(|| byte_trie_prefix(&byte_trie_set, prefixes)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("radix_trie::Trie", &parameter),
+                &prefixes,
+                |b, prefixes| b.iter(|| radix_trie_prefix(&radix_trie_set, prefixes)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("fst::Set", &parameter),
+                &prefixes,
+                |b, prefixes| b.iter(|| fst_prefix(&fst_set, prefixes)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_contains_string, bench_prefix_string);
 criterion_main!(benches);
@@ -1,9 +1,32 @@
-use std::{collections::HashSet, time::Duration};
+mod common;
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{
+    cmp::Ordering,
+    collections::{
+        hash_map::{DefaultHasher, RandomState},
+        HashSet,
+    },
+    hash::{BuildHasher, BuildHasherDefault},
+    iter,
+    time::Duration,
+};
+
+use arrayvec::ArrayVec;
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use cuckoofilter::CuckooFilter;
+use fixedbitset::FixedBitSet;
 use indexmap::IndexSet;
 use itertools::Itertools;
-use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use nohash_hasher::BuildNoHashHasher;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use roaring::{RoaringBitmap, RoaringTreemap};
+use rustc_hash::FxHasher;
+use smallvec::SmallVec;
+use tinyvec::TinyVec;
+
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
 fn vec(source: &[u64], values: &[u64]) -> Vec<bool> {
     let vec = source.iter().cloned().collect_vec();
@@ -19,16 +42,449 @@ fn sorted_vec(source: &[u64], values: &[u64]) -> Vec<bool> {
         .collect()
 }
 
-fn hashset(source: &[u64], values: &[u64]) -> Vec<bool> {
-    let set: HashSet<_> = source.iter().cloned().collect();
+fn smallvec_linear(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let vec: SmallVec<[u64; 32]> = source.iter().cloned().collect();
+    values.iter().map(|it| vec.contains(it)).collect()
+}
+
+fn smallvec_sorted(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let mut vec: SmallVec<[u64; 32]> = source.iter().cloned().collect();
+    vec.sort_unstable();
+    values
+        .iter()
+        .map(|it| vec.binary_search(it).is_ok())
+        .collect()
+}
+
+fn arrayvec_linear(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let vec: ArrayVec<u64, 32> = source.iter().cloned().collect();
+    values.iter().map(|it| vec.contains(it)).collect()
+}
+
+fn arrayvec_sorted(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let mut vec: ArrayVec<u64, 32> = source.iter().cloned().collect();
+    vec.sort_unstable();
+    values
+        .iter()
+        .map(|it| vec.binary_search(it).is_ok())
+        .collect()
+}
+
+fn tinyvec_linear(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let vec: TinyVec<[u64; 32]> = source.iter().cloned().collect();
+    values.iter().map(|it| vec.contains(it)).collect()
+}
+
+fn tinyvec_sorted(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let mut vec: TinyVec<[u64; 32]> = source.iter().cloned().collect();
+    vec.sort_unstable();
+    values
+        .iter()
+        .map(|it| vec.binary_search(it).is_ok())
+        .collect()
+}
+
+fn branchless_search(vec: &[u64], value: u64) -> bool {
+    if vec.is_empty() {
+        return false;
+    }
+    let mut base = 0;
+    let mut len = vec.len();
+    while len > 1 {
+        let half = len / 2;
+        base = if vec[base + half] <= value {
+            base + half
+        } else {
+            base
+        };
+        len -= half;
+    }
+    vec[base] == value
+}
+
+fn branchless_sorted_vec(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let mut vec = source.iter().cloned().collect_vec();
+    vec.sort_unstable();
+    values
+        .iter()
+        .map(|it| branchless_search(&vec, *it))
+        .collect()
+}
+
+fn eytzinger_fill(sorted: &[u64], layout: &mut [u64], next: &mut usize, k: usize) {
+    if k <= sorted.len() {
+        eytzinger_fill(sorted, layout, next, 2 * k);
+        layout[k] = sorted[*next];
+        *next += 1;
+        eytzinger_fill(sorted, layout, next, 2 * k + 1);
+    }
+}
+
+fn eytzinger(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let mut sorted = source.iter().cloned().collect_vec();
+    sorted.sort_unstable();
+    // 1-indexed BFS order, slot 0 is unused.
+    let mut layout = vec![0; sorted.len() + 1];
+    eytzinger_fill(&sorted, &mut layout, &mut 0, 1);
+    values
+        .iter()
+        .map(|it| {
+            let mut k = 1;
+            while k < layout.len() {
+                k = 2 * k + (layout[k] < *it) as usize;
+            }
+            k >>= k.trailing_ones() + 1;
+            k != 0 && layout[k] == *it
+        })
+        .collect()
+}
+
+const S_TREE_B: usize = 16;
+
+fn s_tree_child(i: usize, k: usize) -> usize {
+    k * (S_TREE_B + 1) + i + 1
+}
+
+fn s_tree_fill(sorted: &[u64], nodes: &mut [[u64; S_TREE_B]], next: &mut usize, k: usize) {
+    if k < nodes.len() {
+        for i in 0..S_TREE_B {
+            s_tree_fill(sorted, nodes, next, s_tree_child(i, k));
+            nodes[k][i] = sorted.get(*next).cloned().unwrap_or(u64::MAX);
+            *next += 1;
+        }
+        s_tree_fill(sorted, nodes, next, s_tree_child(S_TREE_B, k));
+    }
+}
+
+fn s_tree(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let mut sorted = source.iter().cloned().collect_vec();
+    sorted.sort_unstable();
+    // Padding uses `u64::MAX`, which never occurs among the generated keys.
+    let mut nodes = vec![[u64::MAX; S_TREE_B]; sorted.len().div_ceil(S_TREE_B)];
+    s_tree_fill(&sorted, &mut nodes, &mut 0, 0);
+    values
+        .iter()
+        .map(|it| {
+            let mut k = 0;
+            let mut candidate = u64::MAX;
+            while k < nodes.len() {
+                let i = nodes[k].iter().filter(|key| **key < *it).count();
+                if i < S_TREE_B {
+                    candidate = nodes[k][i];
+                }
+                k = s_tree_child(i, k);
+            }
+            candidate == *it
+        })
+        .collect()
+}
+
+fn interpolation_search(vec: &[u64], value: u64) -> bool {
+    let (mut low, mut high) = (0, vec.len());
+    while low < high {
+        let (first, last) = (vec[low], vec[high - 1]);
+        if value < first || value > last {
+            return false;
+        }
+        if first == last {
+            return first == value;
+        }
+        let mid = low
+            + ((value - first) as u128 * (high - 1 - low) as u128 / (last - first) as u128)
+                as usize;
+        match vec[mid].cmp(&value) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return true,
+        }
+    }
+    false
+}
+
+fn interpolation_sorted_vec(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let mut vec = source.iter().cloned().collect_vec();
+    vec.sort_unstable();
+    values
+        .iter()
+        .map(|it| interpolation_search(&vec, *it))
+        .collect()
+}
+
+fn hashset<S: BuildHasher + Default>(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let set: HashSet<_, S> = source.iter().cloned().collect();
     values.iter().map(|it| set.contains(it)).collect()
 }
 
-fn indexset(source: &[u64], values: &[u64]) -> Vec<bool> {
-    let set: IndexSet<_> = source.iter().cloned().collect();
+fn indexset<S: BuildHasher + Default>(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let set: IndexSet<_, S> = source.iter().cloned().collect();
     values.iter().map(|it| set.contains(it)).collect()
 }
 
+fn roaring_bitmap(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let bitmap: RoaringBitmap = source.iter().map(|it| *it as u32).collect();
+    values
+        .iter()
+        .map(|it| bitmap.contains(*it as u32))
+        .collect()
+}
+
+fn roaring_treemap(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let treemap: RoaringTreemap = source.iter().cloned().collect();
+    values.iter().map(|it| treemap.contains(*it)).collect()
+}
+
+fn fixedbitset(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let len = source.iter().max().map_or(0, |it| *it as usize + 1);
+    let mut set = FixedBitSet::with_capacity(len);
+    for it in source {
+        set.insert(*it as usize);
+    }
+    values.iter().map(|it| set.contains(*it as usize)).collect()
+}
+
+fn im_hashset(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let set: im::HashSet<u64> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn im_ordset(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let set: im::OrdSet<u64> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn rpds_hash_trie_set(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let set: rpds::HashTrieSet<u64> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn rpds_red_black_tree_set(source: &[u64], values: &[u64]) -> Vec<bool> {
+    let set: rpds::RedBlackTreeSet<u64> = source.iter().cloned().collect();
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+trait Filter {
+    fn may_contain(&self, value: u64) -> bool;
+}
+
+struct BloomFilter {
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    // 10 bits per key with 7 probes targets a false-positive rate of about 1%.
+    const BITS_PER_KEY: usize = 10;
+    const PROBES: u64 = 7;
+
+    fn new(source: &[u64]) -> Self {
+        let mut filter = Self {
+            bits: vec![0; (source.len() * Self::BITS_PER_KEY).div_ceil(64).max(1)],
+        };
+        for it in source {
+            for bit in filter.probes(*it) {
+                filter.bits[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        filter
+    }
+
+    fn probes(&self, value: u64) -> impl Iterator<Item = usize> {
+        let hash = mix(value);
+        let step = hash.rotate_left(32) | 1;
+        let len = self.bits.len() as u64 * 64;
+        (0..Self::PROBES).map(move |i| (hash.wrapping_add(i.wrapping_mul(step)) % len) as usize)
+    }
+}
+
+impl Filter for BloomFilter {
+    fn may_contain(&self, value: u64) -> bool {
+        self.probes(value)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+}
+
+impl Filter for CuckooFilter<DefaultHasher> {
+    fn may_contain(&self, value: u64) -> bool {
+        self.contains(&value)
+    }
+}
+
+fn cuckoo_filter(source: &[u64]) -> CuckooFilter<DefaultHasher> {
+    // Insertion fails once buckets are nearly full, so leave some headroom.
+    let mut filter = CuckooFilter::with_capacity(source.len() + source.len() / 4);
+    for it in source {
+        filter.add(it).unwrap();
+    }
+    filter
+}
+
+/// An 8-bit xor filter: each key's fingerprint is the xor of three slots, one per block.
+struct XorFilter {
+    seed: u64,
+    block_length: usize,
+    fingerprints: Vec<u8>,
+}
+
+impl XorFilter {
+    fn new(source: &[u64]) -> Self {
+        let block_length = (32 + (source.len() as f64 * 1.23).ceil() as usize) / 3;
+        let mut filter = Self {
+            seed: 0,
+            block_length,
+            fingerprints: vec![0; block_length * 3],
+        };
+        let stack = loop {
+            // Peel slots hit by exactly one key; failing to peel every key means retrying with a new seed.
+            let mut counts = vec![0u32; block_length * 3];
+            let mut xor_hashes = vec![0u64; block_length * 3];
+            for it in source {
+                let hash = filter.hash(*it);
+                for slot in filter.slots(hash) {
+                    counts[slot] += 1;
+                    xor_hashes[slot] ^= hash;
+                }
+            }
+            let mut queue = (0..counts.len())
+                .filter(|it| counts[*it] == 1)
+                .collect_vec();
+            let mut stack = Vec::with_capacity(source.len());
+            while let Some(slot) = queue.pop() {
+                if counts[slot] != 1 {
+                    continue;
+                }
+                let hash = xor_hashes[slot];
+                stack.push((slot, hash));
+                for other in filter.slots(hash) {
+                    xor_hashes[other] ^= hash;
+                    counts[other] -= 1;
+                    if counts[other] == 1 {
+                        queue.push(other);
+                    }
+                }
+            }
+            if stack.len() == source.len() {
+                break stack;
+            }
+            filter.seed += 1;
+        };
+        for (slot, hash) in stack.into_iter().rev() {
+            let [a, b, c] = filter.slots(hash);
+            filter.fingerprints[slot] = Self::fingerprint(hash)
+                ^ filter.fingerprints[a]
+                ^ filter.fingerprints[b]
+                ^ filter.fingerprints[c];
+        }
+        filter
+    }
+
+    fn hash(&self, value: u64) -> u64 {
+        mix(value ^ mix(self.seed))
+    }
+
+    fn slots(&self, hash: u64) -> [usize; 3] {
+        [0, 1, 2].map(|i| {
+            let part = hash.rotate_left(21 * i as u32) as u32;
+            ((part as u64 * self.block_length as u64) >> 32) as usize + i * self.block_length
+        })
+    }
+
+    fn fingerprint(hash: u64) -> u8 {
+        (hash ^ (hash >> 32)) as u8
+    }
+}
+
+impl Filter for XorFilter {
+    fn may_contain(&self, value: u64) -> bool {
+        let hash = self.hash(value);
+        let [a, b, c] = self.slots(hash);
+        Self::fingerprint(hash)
+            == self.fingerprints[a] ^ self.fingerprints[b] ^ self.fingerprints[c]
+    }
+}
+
+fn hashset_lookup(set: &HashSet<u64>, values: &[u64]) -> Vec<bool> {
+    values.iter().map(|it| set.contains(it)).collect()
+}
+
+fn sorted_vec_lookup(vec: &[u64], values: &[u64]) -> Vec<bool> {
+    values
+        .iter()
+        .map(|it| vec.binary_search(it).is_ok())
+        .collect()
+}
+
+fn filtered_hashset_lookup<F: Filter>(filter: &F, set: &HashSet<u64>, values: &[u64]) -> Vec<bool> {
+    values
+        .iter()
+        .map(|it| filter.may_contain(*it) && set.contains(it))
+        .collect()
+}
+
+fn filtered_sorted_vec_lookup<F: Filter>(filter: &F, vec: &[u64], values: &[u64]) -> Vec<bool> {
+    values
+        .iter()
+        .map(|it| filter.may_contain(*it) && vec.binary_search(it).is_ok())
+        .collect()
+}
+
+const FALSE_POSITIVE_PROBES: usize = 100_000;
+
+fn report_false_positive_rate<F: Filter>(name: &str, parameter: &str, filter: &F, misses: &[u64]) {
+    let false_positives = misses.iter().filter(|it| filter.may_contain(**it)).count();
+    println!(
+        "contains_trivial {} {}: false positive rate {:.4}",
+        name,
+        parameter,
+        false_positives as f64 / misses.len() as f64
+    );
+}
+
+fn bench_filtered<F: Filter>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    parameter: &str,
+    filter: &F,
+    set: &HashSet<u64>,
+    sorted_items: &[u64],
+    find_items: &[u64],
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("{}+HashSet lookup", name), parameter),
+        find_items,
+        |b, find_items| b.iter(|| filtered_hashset_lookup(filter, set, find_items)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("{}+sorted vec lookup", name), parameter),
+        find_items,
+        |b, find_items| b.iter(|| filtered_sorted_vec_lookup(filter, sorted_items, find_items)),
+    );
+}
+
+fn bench_hashed<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    items: &[u64],
+    find_items: &[u64],
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("HashSet{}", hasher), parameter),
+        &(items, find_items),
+        |b, (items, find_items)| b.iter(|| hashset::<S>(items, find_items)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("IndexSet{}", hasher), parameter),
+        &(items, find_items),
+        |b, (items, find_items)| b.iter(|| indexset::<S>(items, find_items)),
+    );
+}
+
 fn bench_contains(c: &mut Criterion) {
     let mut group = c.benchmark_group("contains_trivial");
     group
@@ -36,73 +492,221 @@ fn bench_contains(c: &mut Criterion) {
         .warm_up_time(Duration::from_millis(500))
         .measurement_time(Duration::from_secs(1));
     let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
-    for item_count in [32, 128, 1024].into_iter() {
-        let items = (0..item_count)
-            .map(|_| rng.gen_range(0..0x80000000u64))
-            .collect_vec();
-        for search_times in [8, 32, 128] {
-            for exist_all_ration in [0.1f64, 0.5, 0.9].into_iter() {
-                let exist_count = ((search_times as f64) * exist_all_ration).round() as u64;
-                if exist_count == 0 {
-                    continue;
-                }
-                let mut find_items = items.iter().take(exist_count as _).cloned().collect_vec();
-                while find_items.len() < search_times {
-                    find_items.push(rng.gen_range(0x8000_0001_u64..0x0001_0000_0000_u64));
+    for density in ["sparse", "clustered", "dense"] {
+        for item_count in [32, 128, 1024, 16384].into_iter() {
+            let items = iter::repeat_with(|| common::gen_id(&mut rng, density, item_count))
+                .unique()
+                .take(item_count as usize)
+                .collect_vec();
+            let item_set: HashSet<_> = items.iter().cloned().collect();
+            let sorted_items = items.iter().cloned().sorted().collect_vec();
+            let bloom_filter = BloomFilter::new(&items);
+            let cuckoo_filter = cuckoo_filter(&items);
+            let xor_filter = XorFilter::new(&items);
+            // The lookup batches hold too few misses for a stable rate, so probe a large batch once.
+            let misses = iter::repeat_with(|| common::gen_id(&mut rng, density, item_count))
+                .filter(|it| !item_set.contains(it))
+                .take(FALSE_POSITIVE_PROBES)
+                .collect_vec();
+            let filter_parameter = format!("{} items,{}", item_count, density);
+            report_false_positive_rate("Bloom", &filter_parameter, &bloom_filter, &misses);
+            report_false_positive_rate("cuckoo", &filter_parameter, &cuckoo_filter, &misses);
+            report_false_positive_rate("xor", &filter_parameter, &xor_filter, &misses);
+            for search_times in [8, 32, 128] {
+                for exist_all_ration in [0.1f64, 0.5, 0.9].into_iter() {
+                    let exist_count = ((search_times as f64) * exist_all_ration).round() as u64;
+                    if exist_count == 0 {
+                        continue;
+                    }
+                    let mut find_items = items.iter().take(exist_count as _).cloned().collect_vec();
+                    while find_items.len() < search_times {
+                        let miss = common::gen_id(&mut rng, density, item_count);
+                        if !item_set.contains(&miss) {
+                            find_items.push(miss);
+                        }
+                    }
+                    find_items.shuffle(&mut rng);
+                    let parameter = format!(
+                        "{} times,{} items,{}% found,{}",
+                        search_times,
+                        item_count,
+                        exist_all_ration * 100f64,
+                        density
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("Vec", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| vec(items, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("sorted vec", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| sorted_vec(items, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("branchless sorted vec", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| {
+                            b.iter(|| branchless_sorted_vec(items, find_items))
+                        },
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("Eytzinger", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| eytzinger(items, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("S-tree", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| s_tree(items, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("interpolation sorted vec", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| {
+                            b.iter(|| interpolation_sorted_vec(items, find_items))
+                        },
+                    );
+                    // Inline storage only holds the small cases; `ArrayVec` cannot spill at all.
+                    if item_count <= 32 {
+                        group.bench_with_input(
+                            BenchmarkId::new("SmallVec", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| smallvec_linear(items, find_items)),
+                        );
+                        group.bench_with_input(
+                            BenchmarkId::new("sorted SmallVec", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| smallvec_sorted(items, find_items)),
+                        );
+                        group.bench_with_input(
+                            BenchmarkId::new("ArrayVec", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| arrayvec_linear(items, find_items)),
+                        );
+                        group.bench_with_input(
+                            BenchmarkId::new("sorted ArrayVec", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| arrayvec_sorted(items, find_items)),
+                        );
+                        group.bench_with_input(
+                            BenchmarkId::new("TinyVec", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| tinyvec_linear(items, find_items)),
+                        );
+                        group.bench_with_input(
+                            BenchmarkId::new("sorted TinyVec", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| tinyvec_sorted(items, find_items)),
+                        );
+                    }
+                    // `RoaringBitmap` only holds 32-bit ids, so larger ones would be truncated.
+                    if items
+                        .iter()
+                        .chain(&find_items)
+                        .all(|it| *it <= u32::MAX as u64)
+                    {
+                        group.bench_with_input(
+                            BenchmarkId::new("RoaringBitmap", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| roaring_bitmap(items, find_items)),
+                        );
+                    }
+                    group.bench_with_input(
+                        BenchmarkId::new("RoaringTreemap", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| roaring_treemap(items, find_items)),
+                    );
+                    // A plain bitset only makes sense while it stays within 64 bits per item.
+                    if items.iter().all(|it| *it < 64 * item_count) {
+                        group.bench_with_input(
+                            BenchmarkId::new("FixedBitSet", &parameter),
+                            &(&items, &find_items),
+                            |b, (items, find_items)| b.iter(|| fixedbitset(items, find_items)),
+                        );
+                    }
+                    group.bench_with_input(
+                        BenchmarkId::new("im::HashSet", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| im_hashset(items, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("im::OrdSet", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| im_ordset(items, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("rpds::HashTrieSet", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| b.iter(|| rpds_hash_trie_set(items, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("rpds::RedBlackTreeSet", &parameter),
+                        &(&items, &find_items),
+                        |b, (items, find_items)| {
+                            b.iter(|| rpds_red_black_tree_set(items, find_items))
+                        },
+                    );
+                    bench_hashed::<RandomState>(&mut group, "", &parameter, &items, &find_items);
+                    bench_hashed::<FxBuildHasher>(
+                        &mut group,
+                        "+FxHash",
+                        &parameter,
+                        &items,
+                        &find_items,
+                    );
+                    bench_hashed::<ahash::RandomState>(
+                        &mut group,
+                        "+AHash",
+                        &parameter,
+                        &items,
+                        &find_items,
+                    );
+                    bench_hashed::<BuildNoHashHasher<u64>>(
+                        &mut group,
+                        "+NoHash",
+                        &parameter,
+                        &items,
+                        &find_items,
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("HashSet lookup", &parameter),
+                        &find_items,
+                        |b, find_items| b.iter(|| hashset_lookup(&item_set, find_items)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("sorted vec lookup", &parameter),
+                        &find_items,
+                        |b, find_items| b.iter(|| sorted_vec_lookup(&sorted_items, find_items)),
+                    );
+                    bench_filtered(
+                        &mut group,
+                        "Bloom",
+                        &parameter,
+                        &bloom_filter,
+                        &item_set,
+                        &sorted_items,
+                        &find_items,
+                    );
+                    bench_filtered(
+                        &mut group,
+                        "cuckoo",
+                        &parameter,
+                        &cuckoo_filter,
+                        &item_set,
+                        &sorted_items,
+                        &find_items,
+                    );
+                    bench_filtered(
+                        &mut group,
+                        "xor",
+                        &parameter,
+                        &xor_filter,
+                        &item_set,
+                        &sorted_items,
+                        &find_items,
+                    );
                 }
-                find_items.shuffle(&mut rng);
-                group.bench_with_input(
-                    BenchmarkId::new(
-                        "Vec",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
-                    &(&items, &find_items),
-                    |b, (items, find_items)| b.iter(|| vec(items, find_items)),
-                );
-                group.bench_with_input(
-                    BenchmarkId::new(
-                        "sorted vec",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
-                    &(&items, &find_items),
-                    |b, (items, find_items)| b.iter(|| sorted_vec(items, find_items)),
-                );
-                group.bench_with_input(
-                    BenchmarkId::new(
-                        "HashSet",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
-                    &(&items, &find_items),
-                    |b, (items, find_items)| b.iter(|| hashset(items, find_items)),
-                );
-                group.bench_with_input(
-                    BenchmarkId::new(
-                        "HashSet",
-                        format!(
-                            "{} times,{} items,{}% found",
-                            search_times,
-                            item_count,
-                            exist_all_ration * 100f64
-                        ),
-                    ),
-                    &(&items, &find_items),
-                    |b, (items, find_items)| b.iter(|| indexset(items, find_items)),
-                );
             }
         }
     }
@@ -1,12 +1,22 @@
+mod common;
+
 use std::{
-    collections::{BTreeSet, HashSet},
+    collections::{hash_map::RandomState, BTreeSet, HashSet},
+    hash::{BuildHasher, BuildHasherDefault},
     time::Duration,
 };
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId,
+    Criterion,
+};
 use indexmap::IndexSet;
 use itertools::Itertools;
 use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use rustc_hash::FxHasher;
+
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
 fn vec_sort(source: &[String]) -> Vec<String> {
     let mut result: Vec<_> = source.to_vec();
@@ -15,18 +25,174 @@ fn vec_sort(source: &[String]) -> Vec<String> {
     result
 }
 
+fn vec_sort_unstable(source: &[String]) -> Vec<String> {
+    let mut result: Vec<_> = source.to_vec();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+fn par_sort_unstable(source: &[String]) -> Vec<String> {
+    let mut result: Vec<_> = source.to_vec();
+    result.par_sort_unstable();
+    result.dedup();
+    result
+}
+
+fn vec_sort_owned(mut source: Vec<String>) -> Vec<String> {
+    source.sort();
+    source.dedup();
+    source
+}
+
+fn itertools_sorted_dedup(source: &[String]) -> Vec<String> {
+    source.iter().cloned().sorted().dedup().collect()
+}
+
+fn btreeset_collect(source: &[String]) -> Vec<String> {
+    source
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
 fn btreeset_auto(source: &[String]) -> BTreeSet<String> {
     source.iter().cloned().collect()
 }
 
-fn hashset_auto(source: &[String]) -> HashSet<String> {
+fn hashset_auto<S: BuildHasher + Default>(source: &[String]) -> HashSet<String, S> {
+    source.iter().cloned().collect()
+}
+
+fn indexset_auto<S: BuildHasher + Default>(source: &[String]) -> IndexSet<String, S> {
+    source.iter().cloned().collect()
+}
+
+fn hashset_extend<S: BuildHasher + Default>(source: &[String]) -> HashSet<String, S> {
+    let mut set = HashSet::default();
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn hashset_with_capacity<S: BuildHasher + Default>(
+    source: &[String],
+    capacity: usize,
+) -> HashSet<String, S> {
+    let mut set = HashSet::with_capacity_and_hasher(capacity, S::default());
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn indexset_extend<S: BuildHasher + Default>(source: &[String]) -> IndexSet<String, S> {
+    let mut set = IndexSet::default();
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn indexset_with_capacity<S: BuildHasher + Default>(
+    source: &[String],
+    capacity: usize,
+) -> IndexSet<String, S> {
+    let mut set = IndexSet::with_capacity_and_hasher(capacity, S::default());
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn hashbrown_auto(source: &[String]) -> hashbrown::HashSet<String> {
+    source.iter().cloned().collect()
+}
+
+fn hashbrown_get_or_insert_with(source: &[String]) -> hashbrown::HashSet<String> {
+    let mut set = hashbrown::HashSet::new();
+    for it in source {
+        set.get_or_insert_with(it.as_str(), str::to_owned);
+    }
+    set
+}
+
+fn im_hashset(source: &[String]) -> im::HashSet<String> {
+    source.iter().cloned().collect()
+}
+
+fn im_ordset(source: &[String]) -> im::OrdSet<String> {
+    source.iter().cloned().collect()
+}
+
+fn rpds_hash_trie_set(source: &[String]) -> rpds::HashTrieSet<String> {
     source.iter().cloned().collect()
 }
 
-fn indexset_auto(source: &[String]) -> IndexSet<String> {
+fn rpds_red_black_tree_set(source: &[String]) -> rpds::RedBlackTreeSet<String> {
     source.iter().cloned().collect()
 }
 
+fn par_fold_hashset(source: &[String]) -> HashSet<String> {
+    source
+        .par_iter()
+        .fold(HashSet::new, |mut set, it| {
+            set.insert(it.clone());
+            set
+        })
+        .reduce(HashSet::new, |a, b| {
+            let (mut larger, smaller) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+            larger.extend(smaller);
+            larger
+        })
+}
+
+fn bench_hashed<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    source: &[String],
+    distinct: usize,
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("HashSet{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashset_auto::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("IndexSet{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| indexset_auto::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("HashSet{}::extend", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashset_extend::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("IndexSet{}::extend", hasher), parameter),
+        source,
+        |b, source| b.iter(|| indexset_extend::<S>(source)),
+    );
+    for (estimate, capacity) in [
+        ("exact", distinct),
+        ("under", distinct / 2),
+        ("over", distinct * 2),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("HashSet{}::with_capacity({})", hasher, estimate),
+                parameter,
+            ),
+            source,
+            |b, source| b.iter(|| hashset_with_capacity::<S>(source, capacity)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("IndexSet{}::with_capacity({})", hasher, estimate),
+                parameter,
+            ),
+            source,
+            |b, source| b.iter(|| indexset_with_capacity::<S>(source, capacity)),
+        );
+    }
+}
+
 fn bench_dedup_string(c: &mut Criterion) {
     let mut group = c.benchmark_group("dedup_string");
     group
@@ -34,6 +200,7 @@ fn bench_dedup_string(c: &mut Criterion) {
         .warm_up_time(Duration::from_millis(500))
         .measurement_time(Duration::from_secs(1));
     let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    let pools = common::thread_pools();
     for item_count in [1, 32, 128, 2048].into_iter() {
         let items = (0..item_count)
             .map(|_| {
@@ -59,26 +226,89 @@ fn bench_dedup_string(c: &mut Criterion) {
                     }
                 }
             }
+            let parameter = format!("{},{}%", item_count, dup_probability);
             group.bench_with_input(
-                BenchmarkId::new("Vec+sort", format!("{},{}%", item_count, dup_probability)),
+                BenchmarkId::new("Vec+sort", &parameter),
                 &source,
                 |b, source| b.iter(|| vec_sort(source)),
             );
             group.bench_with_input(
-                BenchmarkId::new("BTreeSet", format!("{},{}%", item_count, dup_probability)),
+                BenchmarkId::new("Vec+sort_unstable", &parameter),
+                &source,
+                |b, source| b.iter(|| vec_sort_unstable(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("owned Vec+sort", &parameter),
+                &source,
+                |b, source| {
+                    b.iter_batched(|| source.clone(), vec_sort_owned, BatchSize::SmallInput)
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("sorted().dedup()", &parameter),
+                &source,
+                |b, source| b.iter(|| itertools_sorted_dedup(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("BTreeSet+collect", &parameter),
+                &source,
+                |b, source| b.iter(|| btreeset_collect(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("BTreeSet", &parameter),
                 &source,
                 |b, source| b.iter(|| btreeset_auto(source)),
             );
             group.bench_with_input(
-                BenchmarkId::new("HashSet", format!("{},{}%", item_count, dup_probability)),
+                BenchmarkId::new("im::HashSet", &parameter),
                 &source,
-                |b, source| b.iter(|| hashset_auto(source)),
+                |b, source| b.iter(|| im_hashset(source)),
             );
             group.bench_with_input(
-                BenchmarkId::new("IndexSet", format!("{},{}%", item_count, dup_probability)),
+                BenchmarkId::new("im::OrdSet", &parameter),
                 &source,
-                |b, source| b.iter(|| indexset_auto(source)),
+                |b, source| b.iter(|| im_ordset(source)),
             );
+            group.bench_with_input(
+                BenchmarkId::new("rpds::HashTrieSet", &parameter),
+                &source,
+                |b, source| b.iter(|| rpds_hash_trie_set(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("rpds::RedBlackTreeSet", &parameter),
+                &source,
+                |b, source| b.iter(|| rpds_red_black_tree_set(source)),
+            );
+            let distinct = item_count as usize;
+            bench_hashed::<RandomState>(&mut group, "", &parameter, &source, distinct);
+            bench_hashed::<FxBuildHasher>(&mut group, "+FxHash", &parameter, &source, distinct);
+            bench_hashed::<ahash::RandomState>(&mut group, "+AHash", &parameter, &source, distinct);
+            group.bench_with_input(
+                BenchmarkId::new("hashbrown::HashSet", &parameter),
+                &source,
+                |b, source| b.iter(|| hashbrown_auto(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("hashbrown::get_or_insert_with", &parameter),
+                &source,
+                |b, source| b.iter(|| hashbrown_get_or_insert_with(source)),
+            );
+            // Spawning work only has a chance to pay off on the larger inputs.
+            if item_count >= 2048 {
+                for (threads, pool) in &pools {
+                    let parameter = format!("{},{} threads", parameter, threads);
+                    group.bench_with_input(
+                        BenchmarkId::new("par_sort_unstable+dedup", &parameter),
+                        &source,
+                        |b, source| b.iter(|| pool.install(|| par_sort_unstable(source))),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("par fold HashSet+merge", &parameter),
+                        &source,
+                        |b, source| b.iter(|| pool.install(|| par_fold_hashset(source))),
+                    );
+                }
+            }
         }
     }
     group.finish();
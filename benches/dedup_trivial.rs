@@ -1,12 +1,29 @@
+mod common;
+
 use std::{
-    collections::{BTreeSet, HashSet},
+    collections::{hash_map::RandomState, BTreeSet, HashSet},
+    hash::{BuildHasher, BuildHasherDefault},
+    iter,
     time::Duration,
 };
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use arrayvec::ArrayVec;
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId,
+    Criterion,
+};
+use fixedbitset::FixedBitSet;
 use indexmap::IndexSet;
 use itertools::Itertools;
-use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+use nohash_hasher::BuildNoHashHasher;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use roaring::{RoaringBitmap, RoaringTreemap};
+use rustc_hash::FxHasher;
+use smallvec::SmallVec;
+use tinyvec::TinyVec;
+
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
 fn vec_sort(source: &[u64]) -> Vec<u64> {
     let mut result: Vec<_> = source.to_vec();
@@ -15,18 +32,259 @@ fn vec_sort(source: &[u64]) -> Vec<u64> {
     result
 }
 
+fn vec_sort_unstable(source: &[u64]) -> Vec<u64> {
+    let mut result: Vec<_> = source.to_vec();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+fn vec_sort_owned(mut source: Vec<u64>) -> Vec<u64> {
+    source.sort();
+    source.dedup();
+    source
+}
+
+fn par_sort_unstable(source: &[u64]) -> Vec<u64> {
+    let mut result: Vec<_> = source.to_vec();
+    result.par_sort_unstable();
+    result.dedup();
+    result
+}
+
+fn itertools_sorted_dedup(source: &[u64]) -> Vec<u64> {
+    source.iter().cloned().sorted().dedup().collect()
+}
+
+fn btreeset_collect(source: &[u64]) -> Vec<u64> {
+    source
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
 fn btreeset_auto(source: &[u64]) -> BTreeSet<u64> {
     source.iter().cloned().collect()
 }
 
-fn hashset_auto(source: &[u64]) -> HashSet<u64> {
+fn hashset_auto<S: BuildHasher + Default>(source: &[u64]) -> HashSet<u64, S> {
+    source.iter().cloned().collect()
+}
+
+fn indexset_auto<S: BuildHasher + Default>(source: &[u64]) -> IndexSet<u64, S> {
+    source.iter().cloned().collect()
+}
+
+fn hashset_extend<S: BuildHasher + Default>(source: &[u64]) -> HashSet<u64, S> {
+    let mut set = HashSet::default();
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn hashset_with_capacity<S: BuildHasher + Default>(
+    source: &[u64],
+    capacity: usize,
+) -> HashSet<u64, S> {
+    let mut set = HashSet::with_capacity_and_hasher(capacity, S::default());
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn indexset_extend<S: BuildHasher + Default>(source: &[u64]) -> IndexSet<u64, S> {
+    let mut set = IndexSet::default();
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn indexset_with_capacity<S: BuildHasher + Default>(
+    source: &[u64],
+    capacity: usize,
+) -> IndexSet<u64, S> {
+    let mut set = IndexSet::with_capacity_and_hasher(capacity, S::default());
+    set.extend(source.iter().cloned());
+    set
+}
+
+fn vec_linear(source: &[u64]) -> Vec<u64> {
+    let mut result = Vec::new();
+    for it in source {
+        if !result.contains(it) {
+            result.push(*it);
+        }
+    }
+    result
+}
+
+fn vec_sorted(source: &[u64]) -> Vec<u64> {
+    let mut result = Vec::new();
+    for it in source {
+        if let Err(index) = result.binary_search(it) {
+            result.insert(index, *it);
+        }
+    }
+    result
+}
+
+fn smallvec_linear(source: &[u64]) -> SmallVec<[u64; 32]> {
+    let mut result = SmallVec::new();
+    for it in source {
+        if !result.contains(it) {
+            result.push(*it);
+        }
+    }
+    result
+}
+
+fn smallvec_sorted(source: &[u64]) -> SmallVec<[u64; 32]> {
+    let mut result = SmallVec::new();
+    for it in source {
+        if let Err(index) = result.binary_search(it) {
+            result.insert(index, *it);
+        }
+    }
+    result
+}
+
+fn arrayvec_linear(source: &[u64]) -> ArrayVec<u64, 32> {
+    let mut result = ArrayVec::new();
+    for it in source {
+        if !result.contains(it) {
+            result.push(*it);
+        }
+    }
+    result
+}
+
+fn arrayvec_sorted(source: &[u64]) -> ArrayVec<u64, 32> {
+    let mut result = ArrayVec::new();
+    for it in source {
+        if let Err(index) = result.binary_search(it) {
+            result.insert(index, *it);
+        }
+    }
+    result
+}
+
+fn tinyvec_linear(source: &[u64]) -> TinyVec<[u64; 32]> {
+    let mut result = TinyVec::new();
+    for it in source {
+        if !result.contains(it) {
+            result.push(*it);
+        }
+    }
+    result
+}
+
+fn tinyvec_sorted(source: &[u64]) -> TinyVec<[u64; 32]> {
+    let mut result = TinyVec::new();
+    for it in source {
+        if let Err(index) = result.binary_search(it) {
+            result.insert(index, *it);
+        }
+    }
+    result
+}
+
+fn roaring_bitmap(source: &[u64]) -> RoaringBitmap {
+    source.iter().map(|it| *it as u32).collect()
+}
+
+fn roaring_treemap(source: &[u64]) -> RoaringTreemap {
+    source.iter().cloned().collect()
+}
+
+fn fixedbitset(source: &[u64]) -> FixedBitSet {
+    let len = source.iter().max().map_or(0, |it| *it as usize + 1);
+    let mut set = FixedBitSet::with_capacity(len);
+    for it in source {
+        set.insert(*it as usize);
+    }
+    set
+}
+
+fn im_hashset(source: &[u64]) -> im::HashSet<u64> {
+    source.iter().cloned().collect()
+}
+
+fn im_ordset(source: &[u64]) -> im::OrdSet<u64> {
     source.iter().cloned().collect()
 }
 
-fn indexset_auto(source: &[u64]) -> IndexSet<u64> {
+fn rpds_hash_trie_set(source: &[u64]) -> rpds::HashTrieSet<u64> {
     source.iter().cloned().collect()
 }
 
+fn rpds_red_black_tree_set(source: &[u64]) -> rpds::RedBlackTreeSet<u64> {
+    source.iter().cloned().collect()
+}
+
+fn par_fold_hashset(source: &[u64]) -> HashSet<u64> {
+    source
+        .par_iter()
+        .fold(HashSet::new, |mut set, it| {
+            set.insert(*it);
+            set
+        })
+        .reduce(HashSet::new, |a, b| {
+            let (mut larger, smaller) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+            larger.extend(smaller);
+            larger
+        })
+}
+
+fn bench_hashed<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    source: &[u64],
+    distinct: usize,
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("HashSet{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashset_auto::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("IndexSet{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| indexset_auto::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("HashSet{}::extend", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashset_extend::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("IndexSet{}::extend", hasher), parameter),
+        source,
+        |b, source| b.iter(|| indexset_extend::<S>(source)),
+    );
+    for (estimate, capacity) in [
+        ("exact", distinct),
+        ("under", distinct / 2),
+        ("over", distinct * 2),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("HashSet{}::with_capacity({})", hasher, estimate),
+                parameter,
+            ),
+            source,
+            |b, source| b.iter(|| hashset_with_capacity::<S>(source, capacity)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("IndexSet{}::with_capacity({})", hasher, estimate),
+                parameter,
+            ),
+            source,
+            |b, source| b.iter(|| indexset_with_capacity::<S>(source, capacity)),
+        );
+    }
+}
+
 fn bench_dedup_trivial(c: &mut Criterion) {
     let mut group = c.benchmark_group("dedup_trivial");
     group
@@ -34,42 +292,169 @@ fn bench_dedup_trivial(c: &mut Criterion) {
         .warm_up_time(Duration::from_millis(500))
         .measurement_time(Duration::from_secs(1));
     let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
-    for item_count in [1, 32, 128, 2048].into_iter() {
-        let items = (0..item_count).map(|_| rng.next_u64()).collect_vec();
-        for dup_probability in [0, 10, 50, 90, 100, 200, 1000] {
-            let mut source = Vec::new();
-            for item in items.iter() {
-                source.push(*item);
-                if dup_probability < 100 {
-                    if rng.gen_ratio(dup_probability, 100) {
-                        source.push(*item);
+    let pools = common::thread_pools();
+    for density in ["sparse", "clustered", "dense"] {
+        for item_count in [1, 32, 128, 2048, 16384].into_iter() {
+            let items = iter::repeat_with(|| common::gen_id(&mut rng, density, item_count))
+                .unique()
+                .take(item_count as usize)
+                .collect_vec();
+            for dup_probability in [0, 10, 50, 90, 100, 200, 1000] {
+                let mut source = Vec::new();
+                for item in items.iter() {
+                    source.push(*item);
+                    if dup_probability < 100 {
+                        if rng.gen_ratio(dup_probability, 100) {
+                            source.push(*item);
+                        }
+                    } else {
+                        for _ in 0..(dup_probability / 100) {
+                            source.push(*item);
+                        }
                     }
-                } else {
-                    for _ in 0..(dup_probability / 100) {
-                        source.push(*item);
+                }
+                let parameter = format!("{},{}%,{}", item_count, dup_probability, density);
+                group.bench_with_input(
+                    BenchmarkId::new("Vec+sort", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("Vec+sort_unstable", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort_unstable(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("owned Vec+sort", &parameter),
+                    &source,
+                    |b, source| {
+                        b.iter_batched(|| source.clone(), vec_sort_owned, BatchSize::SmallInput)
+                    },
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sorted().dedup()", &parameter),
+                    &source,
+                    |b, source| b.iter(|| itertools_sorted_dedup(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("BTreeSet+collect", &parameter),
+                    &source,
+                    |b, source| b.iter(|| btreeset_collect(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("BTreeSet", &parameter),
+                    &source,
+                    |b, source| b.iter(|| btreeset_auto(source)),
+                );
+                if items.iter().all(|it| *it <= u32::MAX as u64) {
+                    group.bench_with_input(
+                        BenchmarkId::new("RoaringBitmap", &parameter),
+                        &source,
+                        |b, source| b.iter(|| roaring_bitmap(source)),
+                    );
+                }
+                group.bench_with_input(
+                    BenchmarkId::new("RoaringTreemap", &parameter),
+                    &source,
+                    |b, source| b.iter(|| roaring_treemap(source)),
+                );
+                // A plain bitset only makes sense while it stays within 64 bits per item.
+                if items.iter().all(|it| *it < 64 * item_count) {
+                    group.bench_with_input(
+                        BenchmarkId::new("FixedBitSet", &parameter),
+                        &source,
+                        |b, source| b.iter(|| fixedbitset(source)),
+                    );
+                }
+                group.bench_with_input(
+                    BenchmarkId::new("im::HashSet", &parameter),
+                    &source,
+                    |b, source| b.iter(|| im_hashset(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("im::OrdSet", &parameter),
+                    &source,
+                    |b, source| b.iter(|| im_ordset(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("rpds::HashTrieSet", &parameter),
+                    &source,
+                    |b, source| b.iter(|| rpds_hash_trie_set(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("rpds::RedBlackTreeSet", &parameter),
+                    &source,
+                    |b, source| b.iter(|| rpds_red_black_tree_set(source)),
+                );
+                let distinct = item_count as usize;
+                bench_hashed::<RandomState>(&mut group, "", &parameter, &source, distinct);
+                bench_hashed::<FxBuildHasher>(&mut group, "+FxHash", &parameter, &source, distinct);
+                bench_hashed::<ahash::RandomState>(
+                    &mut group, "+AHash", &parameter, &source, distinct,
+                );
+                bench_hashed::<BuildNoHashHasher<u64>>(
+                    &mut group, "+NoHash", &parameter, &source, distinct,
+                );
+                // Inline storage only holds the small cases; `ArrayVec` cannot spill at all.
+                if item_count <= 32 {
+                    group.bench_with_input(
+                        BenchmarkId::new("Vec linear", &parameter),
+                        &source,
+                        |b, source| b.iter(|| vec_linear(source)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("Vec sorted", &parameter),
+                        &source,
+                        |b, source| b.iter(|| vec_sorted(source)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("SmallVec linear", &parameter),
+                        &source,
+                        |b, source| b.iter(|| smallvec_linear(source)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("SmallVec sorted", &parameter),
+                        &source,
+                        |b, source| b.iter(|| smallvec_sorted(source)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("ArrayVec linear", &parameter),
+                        &source,
+                        |b, source| b.iter(|| arrayvec_linear(source)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("ArrayVec sorted", &parameter),
+                        &source,
+                        |b, source| b.iter(|| arrayvec_sorted(source)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("TinyVec linear", &parameter),
+                        &source,
+                        |b, source| b.iter(|| tinyvec_linear(source)),
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("TinyVec sorted", &parameter),
+                        &source,
+                        |b, source| b.iter(|| tinyvec_sorted(source)),
+                    );
+                }
+                // Spawning work only has a chance to pay off on the larger inputs.
+                if item_count >= 2048 {
+                    for (threads, pool) in &pools {
+                        let parameter = format!("{},{} threads", parameter, threads);
+                        group.bench_with_input(
+                            BenchmarkId::new("par_sort_unstable+dedup", &parameter),
+                            &source,
+                            |b, source| b.iter(|| pool.install(|| par_sort_unstable(source))),
+                        );
+                        group.bench_with_input(
+                            BenchmarkId::new("par fold HashSet+merge", &parameter),
+                            &source,
+                            |b, source| b.iter(|| pool.install(|| par_fold_hashset(source))),
+                        );
                     }
                 }
             }
-            group.bench_with_input(
-                BenchmarkId::new("Vec+sort", format!("{},{}%", item_count, dup_probability)),
-                &source,
-                |b, source| b.iter(|| vec_sort(source)),
-            );
-            group.bench_with_input(
-                BenchmarkId::new("BTreeSet", format!("{},{}%", item_count, dup_probability)),
-                &source,
-                |b, source| b.iter(|| btreeset_auto(source)),
-            );
-            group.bench_with_input(
-                BenchmarkId::new("HashSet", format!("{},{}%", item_count, dup_probability)),
-                &source,
-                |b, source| b.iter(|| hashset_auto(source)),
-            );
-            group.bench_with_input(
-                BenchmarkId::new("IndexSet", format!("{},{}%", item_count, dup_probability)),
-                &source,
-                |b, source| b.iter(|| indexset_auto(source)),
-            );
         }
     }
     group.finish();
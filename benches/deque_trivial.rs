@@ -0,0 +1,165 @@
+use std::{
+    collections::{LinkedList, VecDeque},
+    time::Duration,
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+#[derive(Clone, Copy)]
+enum Op {
+    PushBack(u64),
+    PushFront(u64),
+    PopFront,
+    PopBack,
+}
+
+fn vec_deque(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut deque: VecDeque<_> = initial.iter().cloned().collect();
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::PushBack(value) => deque.push_back(*value),
+            Op::PushFront(value) => deque.push_front(*value),
+            Op::PopFront => result.push(deque.pop_front()),
+            Op::PopBack => result.push(deque.pop_back()),
+        }
+    }
+    result
+}
+
+fn vec_remove(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut vec = initial.to_vec();
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::PushBack(value) => vec.push(*value),
+            Op::PushFront(value) => vec.insert(0, *value),
+            Op::PopFront => result.push(if vec.is_empty() {
+                None
+            } else {
+                Some(vec.remove(0))
+            }),
+            Op::PopBack => result.push(vec.pop()),
+        }
+    }
+    result
+}
+
+fn vec_head_index(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut vec = initial.to_vec();
+    let mut head = 0;
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::PushBack(value) => vec.push(*value),
+            Op::PushFront(value) => {
+                if head > 0 {
+                    head -= 1;
+                    vec[head] = *value;
+                } else {
+                    vec.insert(0, *value);
+                }
+            }
+            Op::PopFront => {
+                if head < vec.len() {
+                    result.push(Some(vec[head]));
+                    head += 1;
+                    // Reclaim the consumed prefix once it dominates the buffer.
+                    if head * 2 >= vec.len() {
+                        vec.drain(..head);
+                        head = 0;
+                    }
+                } else {
+                    result.push(None);
+                }
+            }
+            Op::PopBack => result.push(if head < vec.len() { vec.pop() } else { None }),
+        }
+    }
+    result
+}
+
+fn linked_list(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut list: LinkedList<_> = initial.iter().cloned().collect();
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::PushBack(value) => list.push_back(*value),
+            Op::PushFront(value) => list.push_front(*value),
+            Op::PopFront => result.push(list.pop_front()),
+            Op::PopBack => result.push(list.pop_back()),
+        }
+    }
+    result
+}
+
+fn bench_deque_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deque_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for queue_size in [8, 128, 2048].into_iter() {
+        let initial = (0..queue_size).map(|_| rng.next_u64()).collect_vec();
+        for workload in ["fifo", "lifo front", "mixed", "rotate"] {
+            let ops = (0..1024)
+                .map(|i| match workload {
+                    "fifo" => {
+                        if rng.gen_bool(0.5) {
+                            Op::PushBack(rng.next_u64())
+                        } else {
+                            Op::PopFront
+                        }
+                    }
+                    "lifo front" => {
+                        if rng.gen_bool(0.5) {
+                            Op::PushFront(rng.next_u64())
+                        } else {
+                            Op::PopBack
+                        }
+                    }
+                    "mixed" => match rng.gen_range(0..4) {
+                        0 => Op::PushBack(rng.next_u64()),
+                        1 => Op::PushFront(rng.next_u64()),
+                        2 => Op::PopFront,
+                        _ => Op::PopBack,
+                    },
+                    _ => {
+                        if i % 2 == 0 {
+                            Op::PopFront
+                        } else {
+                            Op::PushBack(rng.next_u64())
+                        }
+                    }
+                })
+                .collect_vec();
+            group.bench_with_input(
+                BenchmarkId::new("VecDeque", format!("{},{}", queue_size, workload)),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| vec_deque(initial, ops)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("Vec+remove(0)", format!("{},{}", queue_size, workload)),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| vec_remove(initial, ops)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("Vec+head index", format!("{},{}", queue_size, workload)),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| vec_head_index(initial, ops)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("LinkedList", format!("{},{}", queue_size, workload)),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| linked_list(initial, ops)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_deque_trivial);
+criterion_main!(benches);
@@ -1,9 +1,22 @@
-use std::{collections::HashMap, time::Duration};
+mod common;
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{
+    collections::{hash_map::RandomState, BTreeMap, HashMap},
+    hash::{BuildHasher, BuildHasherDefault},
+    time::Duration,
+};
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use hashbrown::hash_map::RawEntryMut;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use rustc_hash::FxHasher;
+
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
 fn vec(source: &[(String, String)]) -> Vec<(String, Vec<String>)> {
     source
@@ -19,22 +32,334 @@ fn vec(source: &[(String, String)]) -> Vec<(String, Vec<String>)> {
         .collect()
 }
 
-fn hashmap(source: &[(String, String)]) -> HashMap<String, Vec<String>> {
+fn vec_sort(source: &[(String, String)]) -> Vec<(String, Vec<String>)> {
+    let mut sorted = source.iter().collect_vec();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    sorted
+        .into_iter()
+        .group_by(|(it, _)| it)
+        .into_iter()
+        .map(|(key, group)| {
+            (
+                key.clone(),
+                group.into_iter().map(|it| it.1.clone()).collect(),
+            )
+        })
+        .collect()
+}
+
+fn hashmap<S: BuildHasher + Default>(
+    source: &[(String, String)],
+) -> HashMap<String, Vec<String>, S> {
+    let mut result: HashMap<String, Vec<String>, S> = HashMap::default();
+    for (k, v) in source.iter() {
+        result.entry(k.clone()).or_default().push(v.clone());
+    }
+    result
+}
+
+fn hashmap_with_capacity<S: BuildHasher + Default>(
+    source: &[(String, String)],
+    capacity: usize,
+) -> HashMap<String, Vec<String>, S> {
+    let mut result: HashMap<String, Vec<String>, S> =
+        HashMap::with_capacity_and_hasher(capacity, S::default());
+    for (k, v) in source.iter() {
+        result.entry(k.clone()).or_default().push(v.clone());
+    }
+    result
+}
+
+fn hashmap_get_mut(source: &[(String, String)]) -> HashMap<String, Vec<String>> {
     let mut result: HashMap<String, Vec<String>> = HashMap::new();
+    for (k, v) in source.iter() {
+        if let Some(values) = result.get_mut(k) {
+            values.push(v.clone());
+        } else {
+            result.insert(k.clone(), vec![v.clone()]);
+        }
+    }
+    result
+}
+
+fn hashbrown_entry(source: &[(String, String)]) -> hashbrown::HashMap<String, Vec<String>> {
+    let mut result: hashbrown::HashMap<String, Vec<String>> = hashbrown::HashMap::new();
     for (k, v) in source.iter() {
         result.entry(k.clone()).or_default().push(v.clone());
     }
     result
 }
 
-fn indexmap(source: &[(String, String)]) -> IndexMap<String, Vec<String>> {
-    let mut result: IndexMap<String, Vec<String>> = IndexMap::new();
+fn hashbrown_entry_ref(source: &[(String, String)]) -> hashbrown::HashMap<String, Vec<String>> {
+    let mut result: hashbrown::HashMap<String, Vec<String>> = hashbrown::HashMap::new();
+    for (k, v) in source.iter() {
+        result.entry_ref(k.as_str()).or_default().push(v.clone());
+    }
+    result
+}
+
+fn hashbrown_raw_entry(source: &[(String, String)]) -> hashbrown::HashMap<String, Vec<String>> {
+    let mut result: hashbrown::HashMap<String, Vec<String>> = hashbrown::HashMap::new();
+    for (k, v) in source.iter() {
+        match result.raw_entry_mut().from_key(k.as_str()) {
+            RawEntryMut::Occupied(mut entry) => entry.get_mut().push(v.clone()),
+            RawEntryMut::Vacant(entry) => {
+                entry.insert(k.clone(), vec![v.clone()]);
+            }
+        }
+    }
+    result
+}
+
+fn indexmap<S: BuildHasher + Default>(
+    source: &[(String, String)],
+) -> IndexMap<String, Vec<String>, S> {
+    let mut result: IndexMap<String, Vec<String>, S> = IndexMap::default();
+    for (k, v) in source.iter() {
+        result.entry(k.clone()).or_default().push(v.clone());
+    }
+    result
+}
+
+fn btreemap(source: &[(String, String)]) -> BTreeMap<String, Vec<String>> {
+    let mut result: BTreeMap<String, Vec<String>> = BTreeMap::new();
     for (k, v) in source.iter() {
         result.entry(k.clone()).or_default().push(v.clone());
     }
     result
 }
 
+fn hashmap_sorted<S: BuildHasher + Default>(
+    source: &[(String, String)],
+) -> Vec<(String, Vec<String>)> {
+    let mut result = hashmap::<S>(source).into_iter().collect_vec();
+    result.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    result
+}
+
+type NestedGroups = Vec<(String, Vec<(String, Vec<String>)>)>;
+
+fn nested_vec(source: &[(String, String, String)]) -> NestedGroups {
+    let mut sorted = source.iter().collect_vec();
+    sorted.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+    sorted
+        .into_iter()
+        .group_by(|(k1, _, _)| k1)
+        .into_iter()
+        .map(|(k1, group)| {
+            (
+                k1.clone(),
+                group
+                    .group_by(|(_, k2, _)| k2)
+                    .into_iter()
+                    .map(|(k2, group)| (k2.clone(), group.map(|it| it.2.clone()).collect()))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+type NestedHashMap<S> = HashMap<String, HashMap<String, Vec<String>, S>, S>;
+
+fn nested_hashmap<S: BuildHasher + Default>(
+    source: &[(String, String, String)],
+) -> NestedHashMap<S> {
+    let mut result: NestedHashMap<S> = HashMap::default();
+    for (k1, k2, v) in source.iter() {
+        result
+            .entry(k1.clone())
+            .or_default()
+            .entry(k2.clone())
+            .or_default()
+            .push(v.clone());
+    }
+    result
+}
+
+fn flat_hashmap<S: BuildHasher + Default>(
+    source: &[(String, String, String)],
+) -> HashMap<(String, String), Vec<String>, S> {
+    let mut result: HashMap<(String, String), Vec<String>, S> = HashMap::default();
+    for (k1, k2, v) in source.iter() {
+        result
+            .entry((k1.clone(), k2.clone()))
+            .or_default()
+            .push(v.clone());
+    }
+    result
+}
+
+fn flat_hashmap_sorted<S: BuildHasher + Default>(
+    source: &[(String, String, String)],
+) -> Vec<((String, String), Vec<String>)> {
+    let mut result = flat_hashmap::<S>(source).into_iter().collect_vec();
+    result.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    result
+}
+
+type NestedIndexMap<S> = IndexMap<String, IndexMap<String, Vec<String>, S>, S>;
+
+fn nested_indexmap<S: BuildHasher + Default>(
+    source: &[(String, String, String)],
+) -> NestedIndexMap<S> {
+    let mut result: NestedIndexMap<S> = IndexMap::default();
+    for (k1, k2, v) in source.iter() {
+        result
+            .entry(k1.clone())
+            .or_default()
+            .entry(k2.clone())
+            .or_default()
+            .push(v.clone());
+    }
+    result
+}
+
+fn flat_indexmap<S: BuildHasher + Default>(
+    source: &[(String, String, String)],
+) -> IndexMap<(String, String), Vec<String>, S> {
+    let mut result: IndexMap<(String, String), Vec<String>, S> = IndexMap::default();
+    for (k1, k2, v) in source.iter() {
+        result
+            .entry((k1.clone(), k2.clone()))
+            .or_default()
+            .push(v.clone());
+    }
+    result
+}
+
+fn nested_btreemap(
+    source: &[(String, String, String)],
+) -> BTreeMap<String, BTreeMap<String, Vec<String>>> {
+    let mut result: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    for (k1, k2, v) in source.iter() {
+        result
+            .entry(k1.clone())
+            .or_default()
+            .entry(k2.clone())
+            .or_default()
+            .push(v.clone());
+    }
+    result
+}
+
+fn im_hashmap(source: &[(String, String)]) -> im::HashMap<String, Vec<String>> {
+    let mut result: im::HashMap<String, Vec<String>> = im::HashMap::new();
+    for (k, v) in source.iter() {
+        result.entry(k.clone()).or_default().push(v.clone());
+    }
+    result
+}
+
+fn im_ordmap(source: &[(String, String)]) -> im::OrdMap<String, Vec<String>> {
+    let mut result: im::OrdMap<String, Vec<String>> = im::OrdMap::new();
+    for (k, v) in source.iter() {
+        result.entry(k.clone()).or_default().push(v.clone());
+    }
+    result
+}
+
+fn rpds_hash_trie_map(source: &[(String, String)]) -> rpds::HashTrieMap<String, Vec<String>> {
+    let mut result: rpds::HashTrieMap<String, Vec<String>> = rpds::HashTrieMap::new();
+    for (k, v) in source.iter() {
+        if let Some(values) = result.get_mut(k) {
+            values.push(v.clone());
+        } else {
+            result.insert_mut(k.clone(), vec![v.clone()]);
+        }
+    }
+    result
+}
+
+fn par_fold_hashmap(source: &[(String, String)]) -> HashMap<String, Vec<String>> {
+    source
+        .par_iter()
+        .fold(
+            HashMap::new,
+            |mut map: HashMap<String, Vec<String>>, (k, v)| {
+                map.entry(k.clone()).or_default().push(v.clone());
+                map
+            },
+        )
+        // Merging right into left keeps values in source order within each group.
+        .reduce(HashMap::new, |mut left, right| {
+            for (k, mut values) in right {
+                left.entry(k).or_default().append(&mut values);
+            }
+            left
+        })
+}
+
+fn bench_hashed<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    source: &[(String, String)],
+    distinct: usize,
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("Hashmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashmap::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("Indexmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| indexmap::<S>(source)),
+    );
+    for (estimate, capacity) in [
+        ("exact", distinct),
+        ("under", distinct / 2),
+        ("over", distinct * 2),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("Hashmap{}::with_capacity({})", hasher, estimate),
+                parameter,
+            ),
+            source,
+            |b, source| b.iter(|| hashmap_with_capacity::<S>(source, capacity)),
+        );
+    }
+    group.bench_with_input(
+        BenchmarkId::new(format!("Hashmap{}+sort keys", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashmap_sorted::<S>(source)),
+    );
+}
+
+fn bench_hashed_nested<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    source: &[(String, String, String)],
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("nested Hashmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| nested_hashmap::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("flat Hashmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| flat_hashmap::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("flat Hashmap{}+sort keys", hasher), parameter),
+        source,
+        |b, source| b.iter(|| flat_hashmap_sorted::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("nested Indexmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| nested_indexmap::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("flat Indexmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| flat_indexmap::<S>(source)),
+    );
+}
+
 fn bench_group_by_string(c: &mut Criterion) {
     let mut group = c.benchmark_group("group_by_string");
     group
@@ -42,6 +367,7 @@ fn bench_group_by_string(c: &mut Criterion) {
         .warm_up_time(Duration::from_millis(500))
         .measurement_time(Duration::from_secs(1));
     let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    let pools = common::thread_pools();
     for key_count in [8, 32, 128].into_iter() {
         let keys = (0u64..key_count)
             .map(|_| {
@@ -64,34 +390,157 @@ fn bench_group_by_string(c: &mut Criterion) {
                         .collect::<String>()
                 })
                 .collect_vec();
-            let mut source = Vec::new();
+            let mut contiguous = Vec::new();
             for key in &keys {
                 for value in &values {
-                    source.push((key.clone(), value.clone()));
+                    contiguous.push((key.clone(), value.clone()));
+                }
+            }
+            for order in ["contiguous", "clustered", "shuffled", "interleaved"] {
+                let source = common::arrange(&contiguous, values.len(), order, &mut rng);
+                let parameter = format!("{},{}v/k,{}", key_count, value_key_ratio, order);
+                // Plain `group_by` only yields one group per key on contiguous input.
+                if order == "contiguous" {
+                    group.bench_with_input(
+                        BenchmarkId::new("Vec+group_by", &parameter),
+                        &source,
+                        |b, source| b.iter(|| vec(source)),
+                    );
+                }
+                group.bench_with_input(
+                    BenchmarkId::new("Vec+sort+group_by", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("im::HashMap", &parameter),
+                    &source,
+                    |b, source| b.iter(|| im_hashmap(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("im::OrdMap", &parameter),
+                    &source,
+                    |b, source| b.iter(|| im_ordmap(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("rpds::HashTrieMap", &parameter),
+                    &source,
+                    |b, source| b.iter(|| rpds_hash_trie_map(source)),
+                );
+                let distinct = key_count as usize;
+                bench_hashed::<RandomState>(&mut group, "", &parameter, &source, distinct);
+                bench_hashed::<FxBuildHasher>(&mut group, "+FxHash", &parameter, &source, distinct);
+                bench_hashed::<ahash::RandomState>(
+                    &mut group, "+AHash", &parameter, &source, distinct,
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("Hashmap::get_mut+insert", &parameter),
+                    &source,
+                    |b, source| b.iter(|| hashmap_get_mut(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("hashbrown::entry", &parameter),
+                    &source,
+                    |b, source| b.iter(|| hashbrown_entry(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("hashbrown::entry_ref", &parameter),
+                    &source,
+                    |b, source| b.iter(|| hashbrown_entry_ref(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("hashbrown::raw_entry", &parameter),
+                    &source,
+                    |b, source| b.iter(|| hashbrown_raw_entry(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("BTreeMap", &parameter),
+                    &source,
+                    |b, source| b.iter(|| btreemap(source)),
+                );
+                // Spawning work only has a chance to pay off on the larger inputs.
+                if source.len() >= 2048 {
+                    for (threads, pool) in &pools {
+                        group.bench_with_input(
+                            BenchmarkId::new(
+                                "par fold Hashmap+merge",
+                                format!("{},{} threads", parameter, threads),
+                            ),
+                            &source,
+                            |b, source| b.iter(|| pool.install(|| par_fold_hashmap(source))),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    group.finish();
+}
+
+fn bench_group_by_nested_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("group_by_nested_string");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    let random_string = |rng: &mut StdRng| {
+        let len = rng.gen_range(2..32);
+        rng.sample_iter(&Alphanumeric)
+            .take(len)
+            .map(char::from)
+            .collect::<String>()
+    };
+    for tenant_count in [4, 16, 64].into_iter() {
+        let tenants = (0..tenant_count)
+            .map(|_| random_string(&mut rng))
+            .collect_vec();
+        for category_count in [4, 16] {
+            let categories = (0..category_count)
+                .map(|_| random_string(&mut rng))
+                .collect_vec();
+            for value_key_ratio in [1, 8] {
+                let mut contiguous = Vec::new();
+                for tenant in &tenants {
+                    for category in &categories {
+                        for _ in 0..value_key_ratio {
+                            contiguous.push((
+                                tenant.clone(),
+                                category.clone(),
+                                random_string(&mut rng),
+                            ));
+                        }
+                    }
+                }
+                for order in ["contiguous", "clustered", "shuffled", "interleaved"] {
+                    let source = common::arrange(&contiguous, value_key_ratio, order, &mut rng);
+                    let parameter = format!(
+                        "{}x{},{}v/k,{}",
+                        tenant_count, category_count, value_key_ratio, order
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("Vec+sort+group_by", &parameter),
+                        &source,
+                        |b, source| b.iter(|| nested_vec(source)),
+                    );
+                    bench_hashed_nested::<RandomState>(&mut group, "", &parameter, &source);
+                    bench_hashed_nested::<FxBuildHasher>(
+                        &mut group, "+FxHash", &parameter, &source,
+                    );
+                    bench_hashed_nested::<ahash::RandomState>(
+                        &mut group, "+AHash", &parameter, &source,
+                    );
+                    group.bench_with_input(
+                        BenchmarkId::new("nested BTreeMap", &parameter),
+                        &source,
+                        |b, source| b.iter(|| nested_btreemap(source)),
+                    );
                 }
             }
-            group.bench_with_input(
-                BenchmarkId::new(
-                    "Vec+group_by",
-                    format!("{},{}v/k", key_count, value_key_ratio),
-                ),
-                &source,
-                |b, source| b.iter(|| vec(source)),
-            );
-            group.bench_with_input(
-                BenchmarkId::new("Hashmap", format!("{},{}v/k", key_count, value_key_ratio)),
-                &source,
-                |b, source| b.iter(|| hashmap(source)),
-            );
-            group.bench_with_input(
-                BenchmarkId::new("Indexmap", format!("{},{}v/k", key_count, value_key_ratio)),
-                &source,
-                |b, source| b.iter(|| indexmap(source)),
-            );
         }
     }
     group.finish();
 }
 
-criterion_group!(benches, bench_group_by_string);
+criterion_group!(benches, bench_group_by_string, bench_group_by_nested_string);
 criterion_main!(benches);
@@ -1,9 +1,25 @@
-use std::{collections::HashMap, time::Duration};
+mod common;
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{
+    collections::{hash_map::RandomState, BTreeMap, HashMap},
+    hash::{BuildHasher, BuildHasherDefault},
+    time::Duration,
+};
+
+use arrayvec::ArrayVec;
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
 use indexmap::IndexMap;
 use itertools::Itertools;
-use rand::{rngs::StdRng, RngCore, SeedableRng};
+use nohash_hasher::BuildNoHashHasher;
+use rand::{rngs::StdRng, SeedableRng};
+use rayon::prelude::*;
+use rustc_hash::FxHasher;
+use smallvec::SmallVec;
+use tinyvec::TinyVec;
+
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
 fn vec(source: &[(u64, u64)]) -> Vec<(u64, Vec<u64>)> {
     source
@@ -14,63 +30,422 @@ fn vec(source: &[(u64, u64)]) -> Vec<(u64, Vec<u64>)> {
         .collect()
 }
 
-fn hashmap(source: &[(u64, u64)]) -> HashMap<u64, Vec<u64>> {
-    let mut result: HashMap<u64, Vec<u64>> = HashMap::new();
+fn vec_sort(source: &[(u64, u64)]) -> Vec<(u64, Vec<u64>)> {
+    let mut sorted = source.to_vec();
+    sorted.sort_by_key(|it| it.0);
+    vec(&sorted)
+}
+
+fn hashmap<S: BuildHasher + Default>(source: &[(u64, u64)]) -> HashMap<u64, Vec<u64>, S> {
+    let mut result: HashMap<u64, Vec<u64>, S> = HashMap::default();
     for (k, v) in source.iter() {
         result.entry(*k).or_default().push(*v);
     }
     result
 }
 
-fn indexmap(source: &[(u64, u64)]) -> IndexMap<u64, Vec<u64>> {
-    let mut result: IndexMap<u64, Vec<u64>> = IndexMap::new();
+fn hashmap_with_capacity<S: BuildHasher + Default>(
+    source: &[(u64, u64)],
+    capacity: usize,
+) -> HashMap<u64, Vec<u64>, S> {
+    let mut result: HashMap<u64, Vec<u64>, S> =
+        HashMap::with_capacity_and_hasher(capacity, S::default());
     for (k, v) in source.iter() {
         result.entry(*k).or_default().push(*v);
     }
     result
 }
 
+fn hashmap_smallvec(source: &[(u64, u64)]) -> HashMap<u64, SmallVec<[u64; 8]>> {
+    let mut result: HashMap<u64, SmallVec<[u64; 8]>> = HashMap::new();
+    for (k, v) in source.iter() {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+fn hashmap_arrayvec(source: &[(u64, u64)]) -> HashMap<u64, ArrayVec<u64, 8>> {
+    let mut result: HashMap<u64, ArrayVec<u64, 8>> = HashMap::new();
+    for (k, v) in source.iter() {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+fn hashmap_tinyvec(source: &[(u64, u64)]) -> HashMap<u64, TinyVec<[u64; 8]>> {
+    let mut result: HashMap<u64, TinyVec<[u64; 8]>> = HashMap::new();
+    for (k, v) in source.iter() {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+fn indexmap<S: BuildHasher + Default>(source: &[(u64, u64)]) -> IndexMap<u64, Vec<u64>, S> {
+    let mut result: IndexMap<u64, Vec<u64>, S> = IndexMap::default();
+    for (k, v) in source.iter() {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+fn btreemap(source: &[(u64, u64)]) -> BTreeMap<u64, Vec<u64>> {
+    let mut result: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    for (k, v) in source.iter() {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+fn hashmap_sorted<S: BuildHasher + Default>(source: &[(u64, u64)]) -> Vec<(u64, Vec<u64>)> {
+    let mut result = hashmap::<S>(source).into_iter().collect_vec();
+    result.sort_unstable_by_key(|it| it.0);
+    result
+}
+
+#[derive(Clone, Copy)]
+struct Acc {
+    count: u64,
+    sum: u64,
+    min: u64,
+    max: u64,
+}
+
+impl Default for Acc {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0,
+            min: u64::MAX,
+            max: u64::MIN,
+        }
+    }
+}
+
+impl Acc {
+    fn add(mut self, value: u64) -> Self {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self
+    }
+}
+
+fn hashmap_fold<S: BuildHasher + Default>(source: &[(u64, u64)]) -> HashMap<u64, Acc, S> {
+    let mut result: HashMap<u64, Acc, S> = HashMap::default();
+    for (k, v) in source.iter() {
+        let acc = result.entry(*k).or_default();
+        *acc = acc.add(*v);
+    }
+    result
+}
+
+fn indexmap_fold<S: BuildHasher + Default>(source: &[(u64, u64)]) -> IndexMap<u64, Acc, S> {
+    let mut result: IndexMap<u64, Acc, S> = IndexMap::default();
+    for (k, v) in source.iter() {
+        let acc = result.entry(*k).or_default();
+        *acc = acc.add(*v);
+    }
+    result
+}
+
+fn btreemap_fold(source: &[(u64, u64)]) -> BTreeMap<u64, Acc> {
+    let mut result: BTreeMap<u64, Acc> = BTreeMap::new();
+    for (k, v) in source.iter() {
+        let acc = result.entry(*k).or_default();
+        *acc = acc.add(*v);
+    }
+    result
+}
+
+fn hashmap_fold_sorted<S: BuildHasher + Default>(source: &[(u64, u64)]) -> Vec<(u64, Acc)> {
+    let mut result = hashmap_fold::<S>(source).into_iter().collect_vec();
+    result.sort_unstable_by_key(|it| it.0);
+    result
+}
+
+fn grouping_map_fold(source: &[(u64, u64)]) -> HashMap<u64, Acc> {
+    source
+        .iter()
+        .cloned()
+        .into_grouping_map()
+        .fold(Acc::default(), |acc, _, v| acc.add(v))
+}
+
+type SeparateAggregates = (
+    HashMap<u64, usize>,
+    HashMap<u64, u64>,
+    HashMap<u64, u64>,
+    HashMap<u64, u64>,
+);
+
+fn grouping_map_separate(source: &[(u64, u64)]) -> SeparateAggregates {
+    (
+        source.iter().map(|it| it.0).counts(),
+        source.iter().cloned().into_grouping_map().sum(),
+        source.iter().cloned().into_grouping_map().min(),
+        source.iter().cloned().into_grouping_map().max(),
+    )
+}
+
+fn vec_sort_fold(source: &[(u64, u64)]) -> Vec<(u64, Acc)> {
+    let mut sorted = source.to_vec();
+    sorted.sort_unstable_by_key(|it| it.0);
+    sorted
+        .into_iter()
+        .group_by(|it| it.0)
+        .into_iter()
+        .map(|(key, group)| (key, group.fold(Acc::default(), |acc, it| acc.add(it.1))))
+        .collect()
+}
+
+fn im_hashmap(source: &[(u64, u64)]) -> im::HashMap<u64, Vec<u64>> {
+    let mut result: im::HashMap<u64, Vec<u64>> = im::HashMap::new();
+    for (k, v) in source.iter() {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+fn im_ordmap(source: &[(u64, u64)]) -> im::OrdMap<u64, Vec<u64>> {
+    let mut result: im::OrdMap<u64, Vec<u64>> = im::OrdMap::new();
+    for (k, v) in source.iter() {
+        result.entry(*k).or_default().push(*v);
+    }
+    result
+}
+
+fn rpds_hash_trie_map(source: &[(u64, u64)]) -> rpds::HashTrieMap<u64, Vec<u64>> {
+    let mut result: rpds::HashTrieMap<u64, Vec<u64>> = rpds::HashTrieMap::new();
+    for (k, v) in source.iter() {
+        if let Some(values) = result.get_mut(k) {
+            values.push(*v);
+        } else {
+            result.insert_mut(*k, vec![*v]);
+        }
+    }
+    result
+}
+
+fn par_fold_hashmap(source: &[(u64, u64)]) -> HashMap<u64, Vec<u64>> {
+    source
+        .par_iter()
+        .fold(HashMap::new, |mut map: HashMap<u64, Vec<u64>>, (k, v)| {
+            map.entry(*k).or_default().push(*v);
+            map
+        })
+        // Merging right into left keeps values in source order within each group.
+        .reduce(HashMap::new, |mut left, right| {
+            for (k, mut values) in right {
+                left.entry(k).or_default().append(&mut values);
+            }
+            left
+        })
+}
+
+fn bench_hashed<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    source: &[(u64, u64)],
+    distinct: usize,
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("Hashmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashmap::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("Indexmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| indexmap::<S>(source)),
+    );
+    for (estimate, capacity) in [
+        ("exact", distinct),
+        ("under", distinct / 2),
+        ("over", distinct * 2),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("Hashmap{}::with_capacity({})", hasher, estimate),
+                parameter,
+            ),
+            source,
+            |b, source| b.iter(|| hashmap_with_capacity::<S>(source, capacity)),
+        );
+    }
+    group.bench_with_input(
+        BenchmarkId::new(format!("Hashmap{}+sort keys", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashmap_sorted::<S>(source)),
+    );
+}
+
+fn bench_hashed_fold<S: BuildHasher + Default>(
+    group: &mut BenchmarkGroup<WallTime>,
+    hasher: &str,
+    parameter: &str,
+    source: &[(u64, u64)],
+) {
+    group.bench_with_input(
+        BenchmarkId::new(format!("Hashmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashmap_fold::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("Indexmap{}", hasher), parameter),
+        source,
+        |b, source| b.iter(|| indexmap_fold::<S>(source)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new(format!("Hashmap{}+sort keys", hasher), parameter),
+        source,
+        |b, source| b.iter(|| hashmap_fold_sorted::<S>(source)),
+    );
+}
+
+type Input = (u64, usize, &'static str, Vec<(u64, u64)>);
+
+/// Inputs shared by the materializing and aggregating scenarios, so equal ids mean equal data.
+fn inputs() -> Vec<Input> {
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    let mut inputs = Vec::new();
+    for key_count in [8, 32, 128, 1024].into_iter() {
+        // 1 to 32 values per key put the inline `[u64; 8]` groups within, at and past capacity.
+        for per_key in [1, 2, 8, 32, 64] {
+            let contiguous = common::group_by_source(&mut rng, key_count, per_key);
+            for order in ["contiguous", "clustered", "shuffled", "interleaved"] {
+                let source = common::arrange(&contiguous, per_key, order, &mut rng);
+                inputs.push((key_count, per_key, order, source));
+            }
+        }
+    }
+    inputs
+}
+
 fn bench_group_by_trivial(c: &mut Criterion) {
     let mut group = c.benchmark_group("group_by_trivial");
     group
         .sample_size(20)
         .warm_up_time(Duration::from_millis(500))
         .measurement_time(Duration::from_secs(1));
-    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
-    for key_count in [8, 32, 128, 1024].into_iter() {
-        for value_key_ratio in [1, 2, 64] {
-            let keys = (0u64..key_count).map(|_| rng.next_u64()).collect_vec();
-            let values = (0..(key_count * value_key_ratio))
-                .map(|_| rng.next_u64())
-                .collect_vec();
-            let mut source = Vec::new();
-            for key in keys {
-                for value in &values {
-                    source.push((key, *value));
-                }
-            }
+    let pools = common::thread_pools();
+    for (key_count, per_key, order, source) in inputs() {
+        let parameter = format!("{},{}v/k,{}", key_count, per_key, order);
+        // Plain `group_by` only yields one group per key on contiguous input.
+        if order == "contiguous" {
             group.bench_with_input(
-                BenchmarkId::new(
-                    "Vec+group_by",
-                    format!("{},{}v/k", key_count, value_key_ratio),
-                ),
+                BenchmarkId::new("Vec+group_by", &parameter),
                 &source,
                 |b, source| b.iter(|| vec(source)),
             );
+        }
+        group.bench_with_input(
+            BenchmarkId::new("Vec+sort+group_by", &parameter),
+            &source,
+            |b, source| b.iter(|| vec_sort(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("im::HashMap", &parameter),
+            &source,
+            |b, source| b.iter(|| im_hashmap(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("im::OrdMap", &parameter),
+            &source,
+            |b, source| b.iter(|| im_ordmap(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("rpds::HashTrieMap", &parameter),
+            &source,
+            |b, source| b.iter(|| rpds_hash_trie_map(source)),
+        );
+        let distinct = key_count as usize;
+        bench_hashed::<RandomState>(&mut group, "", &parameter, &source, distinct);
+        bench_hashed::<FxBuildHasher>(&mut group, "+FxHash", &parameter, &source, distinct);
+        bench_hashed::<ahash::RandomState>(&mut group, "+AHash", &parameter, &source, distinct);
+        bench_hashed::<BuildNoHashHasher<u64>>(
+            &mut group, "+NoHash", &parameter, &source, distinct,
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Hashmap<SmallVec>", &parameter),
+            &source,
+            |b, source| b.iter(|| hashmap_smallvec(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Hashmap<TinyVec>", &parameter),
+            &source,
+            |b, source| b.iter(|| hashmap_tinyvec(source)),
+        );
+        // `ArrayVec` cannot spill, so it only fits groups of at most 8 values.
+        if per_key <= 8 {
             group.bench_with_input(
-                BenchmarkId::new("Hashmap", format!("{},{}v/k", key_count, value_key_ratio)),
-                &source,
-                |b, source| b.iter(|| hashmap(source)),
-            );
-            group.bench_with_input(
-                BenchmarkId::new("Indexmap", format!("{},{}v/k", key_count, value_key_ratio)),
+                BenchmarkId::new("Hashmap<ArrayVec>", &parameter),
                 &source,
-                |b, source| b.iter(|| indexmap(source)),
+                |b, source| b.iter(|| hashmap_arrayvec(source)),
             );
         }
+        group.bench_with_input(
+            BenchmarkId::new("BTreeMap", &parameter),
+            &source,
+            |b, source| b.iter(|| btreemap(source)),
+        );
+        // Spawning work only has a chance to pay off on the larger inputs.
+        if source.len() >= 2048 {
+            for (threads, pool) in &pools {
+                group.bench_with_input(
+                    BenchmarkId::new(
+                        "par fold Hashmap+merge",
+                        format!("{},{} threads", parameter, threads),
+                    ),
+                    &source,
+                    |b, source| b.iter(|| pool.install(|| par_fold_hashmap(source))),
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+fn bench_group_by_aggregate_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("group_by_aggregate_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    for (key_count, per_key, order, source) in inputs() {
+        let parameter = format!("{},{}v/k,{}", key_count, per_key, order);
+        bench_hashed_fold::<RandomState>(&mut group, "", &parameter, &source);
+        bench_hashed_fold::<FxBuildHasher>(&mut group, "+FxHash", &parameter, &source);
+        bench_hashed_fold::<ahash::RandomState>(&mut group, "+AHash", &parameter, &source);
+        bench_hashed_fold::<BuildNoHashHasher<u64>>(&mut group, "+NoHash", &parameter, &source);
+        group.bench_with_input(
+            BenchmarkId::new("BTreeMap", &parameter),
+            &source,
+            |b, source| b.iter(|| btreemap_fold(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("GroupingMap::fold", &parameter),
+            &source,
+            |b, source| b.iter(|| grouping_map_fold(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("GroupingMap::sum/min/max", &parameter),
+            &source,
+            |b, source| b.iter(|| grouping_map_separate(source)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Vec+sort+group_by", &parameter),
+            &source,
+            |b, source| b.iter(|| vec_sort_fold(source)),
+        );
     }
     group.finish();
 }
 
-criterion_group!(benches, bench_group_by_trivial);
+criterion_group!(
+    benches,
+    bench_group_by_trivial,
+    bench_group_by_aggregate_trivial
+);
 criterion_main!(benches);
@@ -0,0 +1,156 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use linked_hash_map::LinkedHashMap;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+type VecDequeCache = (HashMap<u64, u64>, VecDeque<u64>);
+
+fn indexmap(
+    mut cache: IndexMap<u64, u64>,
+    capacity: usize,
+    trace: &[u64],
+) -> (IndexMap<u64, u64>, usize) {
+    let mut hits = 0;
+    for key in trace {
+        if let Some(value) = cache.shift_remove(key) {
+            cache.insert(*key, value);
+            hits += 1;
+        } else {
+            if cache.len() == capacity {
+                cache.shift_remove_index(0);
+            }
+            cache.insert(*key, key.wrapping_mul(2));
+        }
+    }
+    (cache, hits)
+}
+
+fn hashmap_vecdeque(
+    (mut cache, mut order): VecDequeCache,
+    capacity: usize,
+    trace: &[u64],
+) -> (VecDequeCache, usize) {
+    let mut hits = 0;
+    for key in trace {
+        if cache.contains_key(key) {
+            let position = order.iter().position(|it| it == key).unwrap();
+            order.remove(position);
+            order.push_back(*key);
+            hits += 1;
+        } else {
+            if cache.len() == capacity {
+                let evicted = order.pop_front().unwrap();
+                cache.remove(&evicted);
+            }
+            cache.insert(*key, key.wrapping_mul(2));
+            order.push_back(*key);
+        }
+    }
+    ((cache, order), hits)
+}
+
+fn linked_hash_map(
+    mut cache: LinkedHashMap<u64, u64>,
+    capacity: usize,
+    trace: &[u64],
+) -> (LinkedHashMap<u64, u64>, usize) {
+    let mut hits = 0;
+    for key in trace {
+        if cache.get_refresh(key).is_some() {
+            hits += 1;
+        } else {
+            if cache.len() == capacity {
+                cache.pop_front();
+            }
+            cache.insert(*key, key.wrapping_mul(2));
+        }
+    }
+    (cache, hits)
+}
+
+fn bench_lru_cache_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lru_cache_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for capacity in [16usize, 256, 4096].into_iter() {
+        for hit_rate in [50, 90, 99] {
+            // Uniform accesses over `key_space` keys settle at `capacity / key_space` hits.
+            let key_space = (capacity as f64 * 100f64 / hit_rate as f64).round() as u64;
+            // A key space that fits in the cache never misses once warm.
+            if key_space <= capacity as u64 {
+                continue;
+            }
+            let expected = capacity as f64 * 100f64 / key_space as f64;
+            let trace = (0..(capacity * 16).max(8192))
+                .map(|_| rng.gen_range(0..key_space))
+                .collect_vec();
+            // Every candidate starts from a cache filled by an untimed prefix, skipping cold misses.
+            let warmup = (0..capacity * 4)
+                .map(|_| rng.gen_range(0..key_space))
+                .collect_vec();
+            let warm = indexmap(IndexMap::with_capacity(capacity), capacity, &warmup).0;
+            let parameter = format!("{},{:.1}% expected", capacity, expected);
+            println!(
+                "lru_cache_trivial {}: hit ratio {:.3}",
+                parameter,
+                indexmap(warm, capacity, &trace).1 as f64 / trace.len() as f64
+            );
+            group.throughput(Throughput::Elements(trace.len() as u64));
+            group.bench_with_input(
+                BenchmarkId::new("IndexMap", &parameter),
+                &trace,
+                |b, trace| {
+                    b.iter_batched(
+                        || indexmap(IndexMap::with_capacity(capacity), capacity, &warmup).0,
+                        |cache| indexmap(cache, capacity, trace),
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("HashMap+VecDeque", &parameter),
+                &trace,
+                |b, trace| {
+                    b.iter_batched(
+                        || {
+                            let empty = (
+                                HashMap::with_capacity(capacity),
+                                VecDeque::with_capacity(capacity),
+                            );
+                            hashmap_vecdeque(empty, capacity, &warmup).0
+                        },
+                        |cache| hashmap_vecdeque(cache, capacity, trace),
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("LinkedHashMap", &parameter),
+                &trace,
+                |b, trace| {
+                    b.iter_batched(
+                        || {
+                            let empty = LinkedHashMap::with_capacity(capacity);
+                            linked_hash_map(empty, capacity, &warmup).0
+                        },
+                        |cache| linked_hash_map(cache, capacity, trace),
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_lru_cache_trivial);
+criterion_main!(benches);
@@ -0,0 +1,123 @@
+use std::{collections::HashMap, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn bucket_of(item: u64) -> usize {
+    (item >> 32) as usize
+}
+
+fn vec_of_vecs(source: &[u64], bucket_count: usize) -> Vec<Vec<u64>> {
+    let mut result = vec![Vec::new(); bucket_count];
+    for item in source {
+        result[bucket_of(*item)].push(*item);
+    }
+    result
+}
+
+fn hashmap(source: &[u64]) -> HashMap<usize, Vec<u64>> {
+    let mut result: HashMap<usize, Vec<u64>> = HashMap::new();
+    for item in source {
+        result.entry(bucket_of(*item)).or_default().push(*item);
+    }
+    result
+}
+
+fn itertools_partition(source: &[u64], bucket_count: usize) -> Vec<Vec<u64>> {
+    let mut items = source.to_vec();
+    let mut rest = &mut items[..];
+    let mut result = Vec::with_capacity(bucket_count);
+    for bucket in 0..bucket_count {
+        let split = itertools::partition(rest.iter_mut(), |it| bucket_of(*it) == bucket);
+        let (head, tail) = rest.split_at_mut(split);
+        result.push(head.to_vec());
+        rest = tail;
+    }
+    result
+}
+
+fn sort_split(source: &[u64], bucket_count: usize) -> (Vec<u64>, Vec<usize>) {
+    let mut items = source.to_vec();
+    items.sort_unstable_by_key(|it| bucket_of(*it));
+    let offsets = (0..=bucket_count)
+        .map(|bucket| items.partition_point(|it| bucket_of(*it) < bucket))
+        .collect();
+    (items, offsets)
+}
+
+fn count_then_fill(source: &[u64], bucket_count: usize) -> (Vec<u64>, Vec<usize>) {
+    let mut offsets = vec![0; bucket_count + 1];
+    for item in source {
+        offsets[bucket_of(*item) + 1] += 1;
+    }
+    for bucket in 0..bucket_count {
+        offsets[bucket + 1] += offsets[bucket];
+    }
+    let mut cursors = offsets.clone();
+    let mut items = vec![0; source.len()];
+    for item in source {
+        let cursor = &mut cursors[bucket_of(*item)];
+        items[*cursor] = *item;
+        *cursor += 1;
+    }
+    (items, offsets)
+}
+
+fn bench_partition_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("partition_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [1024, 16384].into_iter() {
+        for bucket_count in [4usize, 64, 1024] {
+            for skew in ["uniform", "80/20", "hot"] {
+                let source = (0..item_count)
+                    .map(|_| {
+                        let bucket = match skew {
+                            "uniform" => rng.gen_range(0..bucket_count),
+                            "80/20" if rng.gen_ratio(80, 100) => {
+                                rng.gen_range(0..(bucket_count / 5).max(1))
+                            }
+                            "hot" if rng.gen_ratio(90, 100) => 0,
+                            _ => rng.gen_range(0..bucket_count),
+                        };
+                        ((bucket as u64) << 32) | rng.gen_range(0..0x1_0000_0000u64)
+                    })
+                    .collect_vec();
+                let parameter = format!("{},{} buckets,{}", item_count, bucket_count, skew);
+                group.bench_with_input(
+                    BenchmarkId::new("Vec<Vec>", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_of_vecs(source, bucket_count)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("Hashmap", &parameter),
+                    &source,
+                    |b, source| b.iter(|| hashmap(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("itertools::partition", &parameter),
+                    &source,
+                    |b, source| b.iter(|| itertools_partition(source, bucket_count)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("Vec+sort+split", &parameter),
+                    &source,
+                    |b, source| b.iter(|| sort_split(source, bucket_count)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("count+fill", &parameter),
+                    &source,
+                    |b, source| b.iter(|| count_then_fill(source, bucket_count)),
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_partition_trivial);
+criterion_main!(benches);
@@ -0,0 +1,173 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+    time::Duration,
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+#[derive(Clone, Copy)]
+enum Op {
+    Push(u64),
+    PopMin,
+    PeekMin,
+}
+
+fn binary_heap(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut heap: BinaryHeap<_> = initial.iter().map(|it| Reverse(*it)).collect();
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::Push(value) => heap.push(Reverse(*value)),
+            Op::PopMin => result.push(heap.pop().map(|it| it.0)),
+            Op::PeekMin => result.push(heap.peek().map(|it| it.0)),
+        }
+    }
+    result
+}
+
+fn btreeset(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut set: BTreeSet<_> = initial.iter().cloned().zip(0usize..).collect();
+    let mut seq = initial.len();
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::Push(value) => {
+                set.insert((*value, seq));
+                seq += 1;
+            }
+            Op::PopMin => result.push(set.pop_first().map(|it| it.0)),
+            Op::PeekMin => result.push(set.first().map(|it| it.0)),
+        }
+    }
+    result
+}
+
+fn btreemap(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut map: BTreeMap<u64, usize> = BTreeMap::new();
+    for value in initial {
+        *map.entry(*value).or_default() += 1;
+    }
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::Push(value) => *map.entry(*value).or_default() += 1,
+            Op::PopMin => result.push(map.first_entry().map(|mut entry| {
+                let key = *entry.key();
+                if *entry.get() == 1 {
+                    entry.remove();
+                } else {
+                    *entry.get_mut() -= 1;
+                }
+                key
+            })),
+            Op::PeekMin => result.push(map.first_key_value().map(|it| *it.0)),
+        }
+    }
+    result
+}
+
+fn sorted_vec(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    // Kept in descending order so the minimum can be popped from the back.
+    let mut vec = initial.to_vec();
+    vec.sort_by(|a, b| b.cmp(a));
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::Push(value) => {
+                let index = vec.partition_point(|it| it > value);
+                vec.insert(index, *value);
+            }
+            Op::PopMin => result.push(vec.pop()),
+            Op::PeekMin => result.push(vec.last().cloned()),
+        }
+    }
+    result
+}
+
+fn unsorted_vec(initial: &[u64], ops: &[Op]) -> Vec<Option<u64>> {
+    let mut vec = initial.to_vec();
+    let mut result = Vec::with_capacity(ops.len());
+    for op in ops {
+        match op {
+            Op::Push(value) => vec.push(*value),
+            Op::PopMin => result.push(
+                vec.iter()
+                    .enumerate()
+                    .min_by_key(|(_, it)| **it)
+                    .map(|(index, _)| index)
+                    .map(|index| vec.swap_remove(index)),
+            ),
+            Op::PeekMin => result.push(vec.iter().min().cloned()),
+        }
+    }
+    result
+}
+
+fn bench_priority_queue_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("priority_queue_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for queue_size in [8, 128, 2048].into_iter() {
+        let initial = (0..queue_size).map(|_| rng.next_u64()).collect_vec();
+        for push_probability in [25, 50, 75] {
+            let ops = (0..1024)
+                .map(|_| {
+                    if rng.gen_ratio(push_probability, 100) {
+                        Op::Push(rng.next_u64())
+                    } else if rng.gen_bool(0.5) {
+                        Op::PopMin
+                    } else {
+                        Op::PeekMin
+                    }
+                })
+                .collect_vec();
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "BinaryHeap",
+                    format!("{},{}% push", queue_size, push_probability),
+                ),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| binary_heap(initial, ops)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "BTreeSet",
+                    format!("{},{}% push", queue_size, push_probability),
+                ),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| btreeset(initial, ops)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "BTreeMap",
+                    format!("{},{}% push", queue_size, push_probability),
+                ),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| btreemap(initial, ops)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "sorted vec",
+                    format!("{},{}% push", queue_size, push_probability),
+                ),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| sorted_vec(initial, ops)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("Vec", format!("{},{}% push", queue_size, push_probability)),
+                &(&initial, &ops),
+                |b, (initial, ops)| b.iter(|| unsorted_vec(initial, ops)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_priority_queue_trivial);
+criterion_main!(benches);
@@ -0,0 +1,133 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    time::Duration,
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use indexmap::IndexSet;
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+fn hashset(a: &HashSet<u64>, b: &HashSet<u64>, subset: bool) -> bool {
+    if subset {
+        a.is_subset(b)
+    } else {
+        a == b
+    }
+}
+
+fn btreeset(a: &BTreeSet<u64>, b: &BTreeSet<u64>, subset: bool) -> bool {
+    if subset {
+        a.is_subset(b)
+    } else {
+        a == b
+    }
+}
+
+fn indexset(a: &IndexSet<u64>, b: &IndexSet<u64>, subset: bool) -> bool {
+    if subset {
+        a.is_subset(b)
+    } else {
+        a == b
+    }
+}
+
+fn sorted_vec(a: &[u64], b: &[u64], subset: bool) -> bool {
+    if subset {
+        let mut b = b.iter();
+        a.iter().all(|x| b.by_ref().find(|y| *y >= x) == Some(x))
+    } else {
+        a == b
+    }
+}
+
+fn vec(a: &[u64], b: &[u64], subset: bool) -> bool {
+    (subset || a.len() == b.len()) && a.iter().all(|it| b.contains(it))
+}
+
+fn bench_set_compare_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("set_compare_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [8, 64, 1024].into_iter() {
+        // Even values only, so `x + 1` is a guaranteed miss that keeps x's sorted position.
+        let items = (0..item_count)
+            .map(|_| rng.gen_range(0..0x4000_0000u64) * 2)
+            .unique()
+            .sorted()
+            .collect_vec();
+        for relation in ["equal", "subset"] {
+            let mut other = items.clone();
+            if relation == "subset" {
+                let extra = (0..item_count)
+                    .map(|_| rng.gen_range(0x8000_0000..0x1_0000_0000u64))
+                    .collect_vec();
+                other.extend(extra);
+            }
+            for mismatch in ["none", "early", "late"] {
+                let mut a = items.clone();
+                match mismatch {
+                    "early" => a[0] += 1,
+                    "late" => *a.last_mut().unwrap() += 1,
+                    _ => {}
+                }
+                let mut b = other.clone();
+                let sorted_a = a.iter().cloned().sorted().collect_vec();
+                let sorted_b = b.iter().cloned().sorted().collect_vec();
+                a.shuffle(&mut rng);
+                b.shuffle(&mut rng);
+                // Put the mismatch back where the insertion-ordered `Vec` and `IndexSet` scan it
+                // first or last; `HashSet` iterates in hash order, so it cannot follow this axis.
+                let mismatched = match mismatch {
+                    "early" => Some((sorted_a[0], 0)),
+                    "late" => Some((*sorted_a.last().unwrap(), a.len() - 1)),
+                    _ => None,
+                };
+                if let Some((value, target)) = mismatched {
+                    let position = a.iter().position(|it| *it == value).unwrap();
+                    a.swap(position, target);
+                }
+                let subset = relation == "subset";
+                let parameter = format!("{},{},{} mismatch", item_count, relation, mismatch);
+                let (hash_a, hash_b): (HashSet<_>, HashSet<_>) =
+                    (a.iter().cloned().collect(), b.iter().cloned().collect());
+                group.bench_with_input(
+                    BenchmarkId::new("HashSet", &parameter),
+                    &(&hash_a, &hash_b),
+                    |bencher, (a, b)| bencher.iter(|| hashset(a, b, subset)),
+                );
+                let (btree_a, btree_b): (BTreeSet<_>, BTreeSet<_>) =
+                    (a.iter().cloned().collect(), b.iter().cloned().collect());
+                group.bench_with_input(
+                    BenchmarkId::new("BTreeSet", &parameter),
+                    &(&btree_a, &btree_b),
+                    |bencher, (a, b)| bencher.iter(|| btreeset(a, b, subset)),
+                );
+                let (index_a, index_b): (IndexSet<_>, IndexSet<_>) =
+                    (a.iter().cloned().collect(), b.iter().cloned().collect());
+                group.bench_with_input(
+                    BenchmarkId::new("IndexSet", &parameter),
+                    &(&index_a, &index_b),
+                    |bencher, (a, b)| bencher.iter(|| indexset(a, b, subset)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sorted vec", &parameter),
+                    &(&sorted_a, &sorted_b),
+                    |bencher, (a, b)| bencher.iter(|| sorted_vec(a, b, subset)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("Vec", &parameter),
+                    &(&a, &b),
+                    |bencher, (a, b)| bencher.iter(|| vec(a, b, subset)),
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_set_compare_trivial);
+criterion_main!(benches);
@@ -0,0 +1,133 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    time::Duration,
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn hashset(set: &HashSet<u64>, value: u64, insert: bool) -> HashSet<u64> {
+    let mut snapshot = set.clone();
+    if insert {
+        snapshot.insert(value);
+    } else {
+        snapshot.remove(&value);
+    }
+    snapshot
+}
+
+fn btreeset(set: &BTreeSet<u64>, value: u64, insert: bool) -> BTreeSet<u64> {
+    let mut snapshot = set.clone();
+    if insert {
+        snapshot.insert(value);
+    } else {
+        snapshot.remove(&value);
+    }
+    snapshot
+}
+
+fn im_hashset(set: &im::HashSet<u64>, value: u64, insert: bool) -> im::HashSet<u64> {
+    let mut snapshot = set.clone();
+    if insert {
+        snapshot.insert(value);
+    } else {
+        snapshot.remove(&value);
+    }
+    snapshot
+}
+
+fn im_ordset(set: &im::OrdSet<u64>, value: u64, insert: bool) -> im::OrdSet<u64> {
+    let mut snapshot = set.clone();
+    if insert {
+        snapshot.insert(value);
+    } else {
+        snapshot.remove(&value);
+    }
+    snapshot
+}
+
+fn rpds_hash_trie_set(
+    set: &rpds::HashTrieSet<u64>,
+    value: u64,
+    insert: bool,
+) -> rpds::HashTrieSet<u64> {
+    if insert {
+        set.insert(value)
+    } else {
+        set.remove(&value)
+    }
+}
+
+fn rpds_red_black_tree_set(
+    set: &rpds::RedBlackTreeSet<u64>,
+    value: u64,
+    insert: bool,
+) -> rpds::RedBlackTreeSet<u64> {
+    if insert {
+        set.insert(value)
+    } else {
+        set.remove(&value)
+    }
+}
+
+fn bench_snapshot_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snapshot_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [32, 1024, 16384].into_iter() {
+        let items = (0..item_count)
+            .map(|_| rng.gen_range(0..0x80000000u64))
+            .collect_vec();
+        let hash: HashSet<u64> = items.iter().cloned().collect();
+        let btree: BTreeSet<u64> = items.iter().cloned().collect();
+        let im_hash: im::HashSet<u64> = items.iter().cloned().collect();
+        let im_ord: im::OrdSet<u64> = items.iter().cloned().collect();
+        let rpds_hash: rpds::HashTrieSet<u64> = items.iter().cloned().collect();
+        let rpds_tree: rpds::RedBlackTreeSet<u64> = items.iter().cloned().collect();
+        for operation in ["insert", "remove"] {
+            let insert = operation == "insert";
+            let value = if insert {
+                rng.gen_range(0x8000_0001_u64..0x0001_0000_0000_u64)
+            } else {
+                items[rng.gen_range(0..items.len())]
+            };
+            let parameter = format!("{},{}", item_count, operation);
+            group.bench_with_input(BenchmarkId::new("HashSet", &parameter), &hash, |b, set| {
+                b.iter_with_large_drop(|| hashset(set, value, insert))
+            });
+            group.bench_with_input(
+                BenchmarkId::new("BTreeSet", &parameter),
+                &btree,
+                |b, set| b.iter_with_large_drop(|| btreeset(set, value, insert)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("im::HashSet", &parameter),
+                &im_hash,
+                |b, set| b.iter_with_large_drop(|| im_hashset(set, value, insert)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("im::OrdSet", &parameter),
+                &im_ord,
+                |b, set| b.iter_with_large_drop(|| im_ordset(set, value, insert)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("rpds::HashTrieSet", &parameter),
+                &rpds_hash,
+                |b, set| b.iter_with_large_drop(|| rpds_hash_trie_set(set, value, insert)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("rpds::RedBlackTreeSet", &parameter),
+                &rpds_tree,
+                |b, set| b.iter_with_large_drop(|| rpds_red_black_tree_set(set, value, insert)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_snapshot_trivial);
+criterion_main!(benches);
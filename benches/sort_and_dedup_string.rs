@@ -1,8 +1,11 @@
+mod common;
+
 use std::{collections::BTreeSet, time::Duration};
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
 use itertools::Itertools;
 use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
 
 fn vec_sort(source: &[String]) -> Vec<String> {
     let mut result: Vec<_> = source.to_vec();
@@ -11,17 +14,51 @@ fn vec_sort(source: &[String]) -> Vec<String> {
     result
 }
 
+fn vec_sort_unstable(source: &[String]) -> Vec<String> {
+    let mut result: Vec<_> = source.to_vec();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+fn par_sort_unstable(source: &[String]) -> Vec<String> {
+    let mut result: Vec<_> = source.to_vec();
+    result.par_sort_unstable();
+    result.dedup();
+    result
+}
+
+fn vec_sort_owned(mut source: Vec<String>) -> Vec<String> {
+    source.sort();
+    source.dedup();
+    source
+}
+
+fn itertools_sorted_dedup(source: &[String]) -> Vec<String> {
+    source.iter().cloned().sorted().dedup().collect()
+}
+
+fn btreeset_collect(source: &[String]) -> Vec<String> {
+    source
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
 fn btreeset_auto(source: &[String]) -> BTreeSet<String> {
     source.iter().cloned().collect()
 }
 
 fn bench_sort_with_dedup(c: &mut Criterion) {
-    let mut group = c.benchmark_group("sort_and_dedup_trivial");
+    let mut group = c.benchmark_group("sort_and_dedup_string");
     group
         .sample_size(20)
         .warm_up_time(Duration::from_secs(1))
         .measurement_time(Duration::from_secs(1));
     let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    let pools = common::thread_pools();
     for item_count in [8, 32, 128, 2048].into_iter() {
         let items = (0..item_count)
             .map(|_| {
@@ -52,11 +89,58 @@ fn bench_sort_with_dedup(c: &mut Criterion) {
                 &source,
                 |b, source| b.iter(|| vec_sort(source)),
             );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "Vec+sort_unstable",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| b.iter(|| vec_sort_unstable(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "owned Vec+sort",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| {
+                    b.iter_batched(|| source.clone(), vec_sort_owned, BatchSize::SmallInput)
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "sorted().dedup()",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| b.iter(|| itertools_sorted_dedup(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "BTreeSet+collect",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| b.iter(|| btreeset_collect(source)),
+            );
             group.bench_with_input(
                 BenchmarkId::new("BTreeSet", format!("{},{}%", item_count, dup_probability)),
                 &source,
                 |b, source| b.iter(|| btreeset_auto(source)),
             );
+            // Spawning work only has a chance to pay off on the larger inputs.
+            if item_count >= 2048 {
+                for (threads, pool) in &pools {
+                    group.bench_with_input(
+                        BenchmarkId::new(
+                            "par_sort_unstable+dedup",
+                            format!("{},{}%,{} threads", item_count, dup_probability, threads),
+                        ),
+                        &source,
+                        |b, source| b.iter(|| pool.install(|| par_sort_unstable(source))),
+                    );
+                }
+            }
         }
     }
     group.finish();
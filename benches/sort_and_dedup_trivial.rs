@@ -1,8 +1,14 @@
+mod common;
+
 use std::{collections::BTreeSet, time::Duration};
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use arrayvec::ArrayVec;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
 use itertools::Itertools;
 use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+use rayon::prelude::*;
+use smallvec::SmallVec;
+use tinyvec::TinyVec;
 
 fn vec_sort(source: &[u64]) -> Vec<u64> {
     let mut result: Vec<_> = source.to_vec();
@@ -11,6 +17,69 @@ fn vec_sort(source: &[u64]) -> Vec<u64> {
     result
 }
 
+fn vec_sort_unstable(source: &[u64]) -> Vec<u64> {
+    let mut result: Vec<_> = source.to_vec();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+fn vec_sort_owned(mut source: Vec<u64>) -> Vec<u64> {
+    source.sort();
+    source.dedup();
+    source
+}
+
+fn par_sort_unstable(source: &[u64]) -> Vec<u64> {
+    let mut result: Vec<_> = source.to_vec();
+    result.par_sort_unstable();
+    result.dedup();
+    result
+}
+
+fn itertools_sorted_dedup(source: &[u64]) -> Vec<u64> {
+    source.iter().cloned().sorted().dedup().collect()
+}
+
+fn btreeset_collect(source: &[u64]) -> Vec<u64> {
+    source
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn smallvec_sorted(source: &[u64]) -> SmallVec<[u64; 32]> {
+    let mut result = SmallVec::new();
+    for it in source {
+        if let Err(index) = result.binary_search(it) {
+            result.insert(index, *it);
+        }
+    }
+    result
+}
+
+fn arrayvec_sorted(source: &[u64]) -> ArrayVec<u64, 32> {
+    let mut result = ArrayVec::new();
+    for it in source {
+        if let Err(index) = result.binary_search(it) {
+            result.insert(index, *it);
+        }
+    }
+    result
+}
+
+fn tinyvec_sorted(source: &[u64]) -> TinyVec<[u64; 32]> {
+    let mut result = TinyVec::new();
+    for it in source {
+        if let Err(index) = result.binary_search(it) {
+            result.insert(index, *it);
+        }
+    }
+    result
+}
+
 fn btreeset_auto(source: &[u64]) -> BTreeSet<u64> {
     source.iter().cloned().collect()
 }
@@ -22,7 +91,8 @@ fn bench_sort_with_dedup(c: &mut Criterion) {
         .warm_up_time(Duration::from_secs(1))
         .measurement_time(Duration::from_secs(1));
     let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
-    for item_count in [8, 32, 128, 2048].into_iter() {
+    let pools = common::thread_pools();
+    for item_count in [8, 32, 128, 2048, 16384].into_iter() {
         let items = (0..item_count).map(|_| rng.next_u64()).collect_vec();
         for dup_probability in [0, 10, 50, 90, 100, 200, 1000] {
             let mut source = Vec::new();
@@ -43,11 +113,85 @@ fn bench_sort_with_dedup(c: &mut Criterion) {
                 &source,
                 |b, source| b.iter(|| vec_sort(source)),
             );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "Vec+sort_unstable",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| b.iter(|| vec_sort_unstable(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "owned Vec+sort",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| {
+                    b.iter_batched(|| source.clone(), vec_sort_owned, BatchSize::SmallInput)
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "sorted().dedup()",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| b.iter(|| itertools_sorted_dedup(source)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(
+                    "BTreeSet+collect",
+                    format!("{},{}%", item_count, dup_probability),
+                ),
+                &source,
+                |b, source| b.iter(|| btreeset_collect(source)),
+            );
+            // Inline storage only holds the small cases; `ArrayVec` cannot spill at all.
+            if item_count <= 32 {
+                group.bench_with_input(
+                    BenchmarkId::new(
+                        "SmallVec sorted",
+                        format!("{},{}%", item_count, dup_probability),
+                    ),
+                    &source,
+                    |b, source| b.iter(|| smallvec_sorted(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new(
+                        "ArrayVec sorted",
+                        format!("{},{}%", item_count, dup_probability),
+                    ),
+                    &source,
+                    |b, source| b.iter(|| arrayvec_sorted(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new(
+                        "TinyVec sorted",
+                        format!("{},{}%", item_count, dup_probability),
+                    ),
+                    &source,
+                    |b, source| b.iter(|| tinyvec_sorted(source)),
+                );
+            }
             group.bench_with_input(
                 BenchmarkId::new("BTreeSet", format!("{},{}%", item_count, dup_probability)),
                 &source,
                 |b, source| b.iter(|| btreeset_auto(source)),
             );
+            // Spawning work only has a chance to pay off on the larger inputs.
+            if item_count >= 2048 {
+                for (threads, pool) in &pools {
+                    group.bench_with_input(
+                        BenchmarkId::new(
+                            "par_sort_unstable+dedup",
+                            format!("{},{}%,{} threads", item_count, dup_probability, threads),
+                        ),
+                        &source,
+                        |b, source| b.iter(|| pool.install(|| par_sort_unstable(source))),
+                    );
+                }
+            }
         }
     }
     group.finish();
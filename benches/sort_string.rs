@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use itertools::Itertools;
+use rand::{distributions::Alphanumeric, rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+fn vec_sort(source: &[String]) -> Vec<String> {
+    let mut result = source.to_vec();
+    result.sort();
+    result
+}
+
+fn vec_sort_unstable(source: &[String]) -> Vec<String> {
+    let mut result = source.to_vec();
+    result.sort_unstable();
+    result
+}
+
+fn vec_sort_by_cached_key(source: &[String]) -> Vec<String> {
+    let mut result = source.to_vec();
+    result.sort_by_cached_key(|it| it.clone());
+    result
+}
+
+fn vec_sort_by_key(source: &[String]) -> Vec<String> {
+    let mut result = source.to_vec();
+    result.sort_by_key(|it| it.clone());
+    result
+}
+
+fn bench_sort_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sort_string");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [8, 32, 128, 2048].into_iter() {
+        let items = (0..item_count)
+            .map(|_| {
+                let len = rng.gen_range(2..32);
+                (&mut rng)
+                    .sample_iter(&Alphanumeric)
+                    .take(len)
+                    .map(char::from)
+                    .collect::<String>()
+            })
+            .collect_vec();
+        for dup_probability in [0, 10, 50, 90, 100, 200, 1000] {
+            let mut source = Vec::new();
+            for item in items.iter() {
+                source.push(item.clone());
+                if dup_probability < 100 {
+                    if rng.gen_ratio(dup_probability, 100) {
+                        source.push(item.clone());
+                    }
+                } else {
+                    for _ in 0..(dup_probability / 100) {
+                        source.push(item.clone());
+                    }
+                }
+            }
+            for order in ["random", "sorted", "reversed"] {
+                match order {
+                    "random" => source.shuffle(&mut rng),
+                    "sorted" => source.sort_unstable(),
+                    _ => source.reverse(),
+                }
+                let parameter = format!("{},{}%,{}", item_count, dup_probability, order);
+                group.bench_with_input(
+                    BenchmarkId::new("sort", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sort_unstable", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort_unstable(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sort_by_cached_key", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort_by_cached_key(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sort_by_key", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort_by_key(source)),
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sort_string);
+criterion_main!(benches);
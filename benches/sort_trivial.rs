@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+
+fn vec_sort(source: &[u64]) -> Vec<u64> {
+    let mut result = source.to_vec();
+    result.sort();
+    result
+}
+
+fn vec_sort_unstable(source: &[u64]) -> Vec<u64> {
+    let mut result = source.to_vec();
+    result.sort_unstable();
+    result
+}
+
+fn vec_sort_by_cached_key(source: &[u64]) -> Vec<u64> {
+    let mut result = source.to_vec();
+    result.sort_by_cached_key(|it| *it);
+    result
+}
+
+fn vec_sort_by_key(source: &[u64]) -> Vec<u64> {
+    let mut result = source.to_vec();
+    result.sort_by_key(|it| *it);
+    result
+}
+
+fn radix_sort(source: &[u64]) -> Vec<u64> {
+    let mut result = source.to_vec();
+    let mut buffer = vec![0; source.len()];
+    for shift in (0..64).step_by(8) {
+        let mut offsets = [0usize; 257];
+        for it in &result {
+            offsets[((it >> shift) & 0xff) as usize + 1] += 1;
+        }
+        // Every item shares this digit, so the pass would not reorder anything.
+        if offsets.contains(&result.len()) {
+            continue;
+        }
+        for digit in 0..256 {
+            offsets[digit + 1] += offsets[digit];
+        }
+        for it in &result {
+            let offset = &mut offsets[((it >> shift) & 0xff) as usize];
+            buffer[*offset] = *it;
+            *offset += 1;
+        }
+        std::mem::swap(&mut result, &mut buffer);
+    }
+    result
+}
+
+fn bench_sort_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sort_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for item_count in [8, 32, 128, 2048].into_iter() {
+        let items = (0..item_count).map(|_| rng.next_u64()).collect_vec();
+        for dup_probability in [0, 10, 50, 90, 100, 200, 1000] {
+            let mut source = Vec::new();
+            for item in items.iter() {
+                source.push(*item);
+                if dup_probability < 100 {
+                    if rng.gen_ratio(dup_probability, 100) {
+                        source.push(*item);
+                    }
+                } else {
+                    for _ in 0..(dup_probability / 100) {
+                        source.push(*item);
+                    }
+                }
+            }
+            for order in ["random", "sorted", "reversed"] {
+                match order {
+                    "random" => source.shuffle(&mut rng),
+                    "sorted" => source.sort_unstable(),
+                    _ => source.reverse(),
+                }
+                let parameter = format!("{},{}%,{}", item_count, dup_probability, order);
+                group.bench_with_input(
+                    BenchmarkId::new("sort", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sort_unstable", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort_unstable(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sort_by_cached_key", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort_by_cached_key(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sort_by_key", &parameter),
+                    &source,
+                    |b, source| b.iter(|| vec_sort_by_key(source)),
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("radix sort", &parameter),
+                    &source,
+                    |b, source| b.iter(|| radix_sort(source)),
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sort_trivial);
+criterion_main!(benches);
@@ -0,0 +1,207 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, RngCore, SeedableRng};
+
+#[derive(Clone, Copy)]
+enum Update {
+    Count,
+    Max,
+    ReplaceEven,
+}
+
+impl Update {
+    fn name(self) -> &'static str {
+        match self {
+            Update::Count => "count",
+            Update::Max => "max",
+            Update::ReplaceEven => "replace even",
+        }
+    }
+
+    fn initial(self, value: u64) -> u64 {
+        match self {
+            Update::Count => 1,
+            Update::Max | Update::ReplaceEven => value,
+        }
+    }
+
+    fn apply(self, slot: &mut u64, value: u64) {
+        match self {
+            Update::Count => *slot += 1,
+            Update::Max => *slot = (*slot).max(value),
+            Update::ReplaceEven => {
+                if value & 1 == 0 {
+                    *slot = value;
+                }
+            }
+        }
+    }
+}
+
+fn hashmap_entry(
+    mut map: HashMap<u64, u64>,
+    updates: &[(u64, u64)],
+    update: Update,
+) -> HashMap<u64, u64> {
+    for (k, v) in updates {
+        map.entry(*k)
+            .and_modify(|slot| update.apply(slot, *v))
+            .or_insert_with(|| update.initial(*v));
+    }
+    map
+}
+
+fn hashmap_get_mut(
+    mut map: HashMap<u64, u64>,
+    updates: &[(u64, u64)],
+    update: Update,
+) -> HashMap<u64, u64> {
+    for (k, v) in updates {
+        if let Some(slot) = map.get_mut(k) {
+            update.apply(slot, *v);
+        } else {
+            map.insert(*k, update.initial(*v));
+        }
+    }
+    map
+}
+
+fn indexmap_entry(
+    mut map: IndexMap<u64, u64>,
+    updates: &[(u64, u64)],
+    update: Update,
+) -> IndexMap<u64, u64> {
+    for (k, v) in updates {
+        map.entry(*k)
+            .and_modify(|slot| update.apply(slot, *v))
+            .or_insert_with(|| update.initial(*v));
+    }
+    map
+}
+
+fn btreemap_entry(
+    mut map: BTreeMap<u64, u64>,
+    updates: &[(u64, u64)],
+    update: Update,
+) -> BTreeMap<u64, u64> {
+    for (k, v) in updates {
+        map.entry(*k)
+            .and_modify(|slot| update.apply(slot, *v))
+            .or_insert_with(|| update.initial(*v));
+    }
+    map
+}
+
+fn sorted_vec(mut vec: Vec<(u64, u64)>, updates: &[(u64, u64)], update: Update) -> Vec<(u64, u64)> {
+    for (k, v) in updates {
+        match vec.binary_search_by_key(k, |it| it.0) {
+            Ok(index) => update.apply(&mut vec[index].1, *v),
+            Err(index) => vec.insert(index, (*k, update.initial(*v))),
+        }
+    }
+    vec
+}
+
+fn bench_upsert_trivial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("upsert_trivial");
+    group
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    let mut rng = StdRng::from_seed(*b"42424242424242424242424242424242");
+    for key_count in [8, 128, 2048].into_iter() {
+        let entries = (0..key_count)
+            .map(|_| (rng.gen_range(0..0x80000000u64), rng.next_u64()))
+            .collect_vec();
+        let hashmap: HashMap<_, _> = entries.iter().cloned().collect();
+        let indexmap: IndexMap<_, _> = entries.iter().cloned().collect();
+        let btreemap: BTreeMap<_, _> = entries.iter().cloned().collect();
+        let sorted_entries = btreemap.iter().map(|(k, v)| (*k, *v)).collect_vec();
+        for existing_ratio in [0f64, 0.5, 0.9, 1.0].into_iter() {
+            let existing_count = (1024f64 * existing_ratio).round() as usize;
+            let mut updates = (0..existing_count)
+                .map(|_| (entries[rng.gen_range(0..entries.len())].0, rng.next_u64()))
+                .collect_vec();
+            while updates.len() < 1024 {
+                updates.push((
+                    rng.gen_range(0x8000_0001_u64..0x0001_0000_0000_u64),
+                    rng.next_u64(),
+                ));
+            }
+            updates.shuffle(&mut rng);
+            for update in [Update::Count, Update::Max, Update::ReplaceEven] {
+                let parameter = format!(
+                    "{},{}% existing,{}",
+                    key_count,
+                    existing_ratio * 100f64,
+                    update.name()
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("HashMap::entry", &parameter),
+                    &updates,
+                    |b, updates| {
+                        b.iter_batched(
+                            || hashmap.clone(),
+                            |map| hashmap_entry(map, updates, update),
+                            BatchSize::SmallInput,
+                        )
+                    },
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("HashMap::get_mut+insert", &parameter),
+                    &updates,
+                    |b, updates| {
+                        b.iter_batched(
+                            || hashmap.clone(),
+                            |map| hashmap_get_mut(map, updates, update),
+                            BatchSize::SmallInput,
+                        )
+                    },
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("IndexMap::entry", &parameter),
+                    &updates,
+                    |b, updates| {
+                        b.iter_batched(
+                            || indexmap.clone(),
+                            |map| indexmap_entry(map, updates, update),
+                            BatchSize::SmallInput,
+                        )
+                    },
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("BTreeMap::entry", &parameter),
+                    &updates,
+                    |b, updates| {
+                        b.iter_batched(
+                            || btreemap.clone(),
+                            |map| btreemap_entry(map, updates, update),
+                            BatchSize::SmallInput,
+                        )
+                    },
+                );
+                group.bench_with_input(
+                    BenchmarkId::new("sorted vec", &parameter),
+                    &updates,
+                    |b, updates| {
+                        b.iter_batched(
+                            || sorted_entries.clone(),
+                            |vec| sorted_vec(vec, updates, update),
+                            BatchSize::SmallInput,
+                        )
+                    },
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_upsert_trivial);
+criterion_main!(benches);